@@ -0,0 +1,110 @@
+// mod actions;
+
+//! Actions that change the state of processes
+
+use std::fs::write;
+use std::io::Error;
+
+/// Range accepted by `/proc/<pid>/oom_score_adj`
+pub const OOM_SCORE_ADJ_RANGE: std::ops::RangeInclusive<i32> = -1000..=1000;
+
+/// Writes `/proc/<pid>/oom_score_adj`. Lowering it requires
+/// `CAP_SYS_RESOURCE`, so expect `PermissionDenied`.
+pub fn set_oom_score_adj(pid: i32, oom_score_adj: i32) -> Result<(), Error> {
+  write(format!("/proc/{pid}/oom_score_adj"), oom_score_adj.to_string())
+}
+
+/// Range of nice values
+pub const NICE_RANGE: std::ops::RangeInclusive<i32> = -20..=19;
+
+/// Sets the nice value of a thread with setpriority(2), lowering it requires
+/// `CAP_SYS_NICE`
+pub fn set_nice(pid: i32, nice: i32) -> Result<(), Error> {
+  let result = unsafe {
+    libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice)
+  };
+
+  if result == 0 { Ok(()) } else { Err(Error::last_os_error()) }
+}
+
+/// Sets one of the non real time scheduling policies (`SCHED_OTHER`,
+/// `SCHED_BATCH` or `SCHED_IDLE`) of a thread with sched_setscheduler(2)
+pub fn set_policy(pid: i32, policy: i32) -> Result<(), Error> {
+  let param = libc::sched_param { sched_priority: 0 };
+  let result = unsafe { libc::sched_setscheduler(pid, policy, &param) };
+
+  if result == 0 { Ok(()) } else { Err(Error::last_os_error()) }
+}
+
+/// CPUs a `cpu_set_t` can hold, higher ones can't be in an affinity
+pub const MAX_CPUS: usize = 8 * std::mem::size_of::<libc::cpu_set_t>();
+
+/// Restricts a process (or thread) to `cpus` with sched_setaffinity(2)
+pub fn set_affinity(pid: i32, cpus: &[usize]) -> Result<(), Error> {
+  if cpus.iter().any(|&cpu| cpu >= MAX_CPUS) {
+    return Err(Error::from_raw_os_error(libc::EINVAL));
+  }
+
+  let result = unsafe {
+    let mut cpu_set: libc::cpu_set_t = std::mem::zeroed();
+    for &cpu in cpus {
+      libc::CPU_SET(cpu, &mut cpu_set);
+    }
+    libc::sched_setaffinity(
+      pid, std::mem::size_of::<libc::cpu_set_t>(), &cpu_set
+    )
+  };
+
+  if result == 0 { Ok(()) } else { Err(Error::last_os_error()) }
+}
+
+/// Signals offered in the signal menu
+pub const SIGNALS: [(&str, i32); 8] = [
+  ("TERM", libc::SIGTERM),
+  ("KILL", libc::SIGKILL),
+  ("INT", libc::SIGINT),
+  ("HUP", libc::SIGHUP),
+  ("STOP", libc::SIGSTOP),
+  ("CONT", libc::SIGCONT),
+  ("USR1", libc::SIGUSR1),
+  ("USR2", libc::SIGUSR2)
+];
+
+/// SIGRTMAX on Linux
+const MAX_SIGNAL: i32 = 64;
+
+/// Parses a signal from the menu by name (with or without the `SIG` prefix,
+/// any case) or number
+pub fn parse_signal(input: &str) -> Option<i32> {
+  let input = input.trim().to_ascii_uppercase();
+  let name = input.strip_prefix("SIG").unwrap_or(&input);
+
+  if let Some((_, signal)) = SIGNALS.iter().find(|(n, _)| *n == name) {
+    return Some(*signal);
+  }
+
+  match name.parse() {
+    Ok(signal) if signal > 0 && signal <= MAX_SIGNAL => Some(signal),
+    _ => None
+  }
+}
+
+/// Sends `signal` with kill(2), expect `EPERM` for processes of other users
+/// and `ESRCH` for processes that are gone
+pub fn send_signal(pid: i32, signal: i32) -> Result<(), Error> {
+  let result = unsafe { libc::kill(pid, signal) };
+
+  if result == 0 { Ok(()) } else { Err(Error::last_os_error()) }
+}
+
+#[test]
+fn parse_signal_returns_expected_values() {
+  assert_eq!(Some(libc::SIGTERM), parse_signal("TERM"));
+  assert_eq!(Some(libc::SIGKILL), parse_signal("sigkill"));
+  assert_eq!(Some(libc::SIGUSR1), parse_signal(" usr1 "));
+  assert_eq!(Some(9), parse_signal("9"));
+  assert_eq!(None, parse_signal("0"));
+  assert_eq!(None, parse_signal("1000"));
+  assert_eq!(None, parse_signal("TERMINATE"));
+  assert_eq!(None, parse_signal(""));
+}
@@ -0,0 +1,107 @@
+// mod error;
+//! Errors reading the state of the system
+
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// A file under /proc or /sys that could not be read or understood
+#[derive(Debug)]
+pub enum MonitorError {
+  /// The file could not be read
+  Read { path: PathBuf, source: io::Error },
+  /// The file was read, but `context` could not be parsed from its content
+  Parse { path: PathBuf, context: String },
+  /// `count` errors of the same refresh, shown as the first one
+  Many { first: Box<MonitorError>, count: usize }
+}
+
+impl MonitorError {
+
+  pub fn read(path: &Path, source: io::Error) -> MonitorError {
+    MonitorError::Read { path: path.to_path_buf(), source }
+  }
+
+  pub fn parse(path: &Path, error: ParseError) -> MonitorError {
+    MonitorError::Parse { path: path.to_path_buf(), context: error.context }
+  }
+
+  /// Combines `errors` into one, `None` if there are none
+  pub fn combine(mut errors: Vec<MonitorError>) -> Option<MonitorError> {
+    match errors.len() {
+      0 => None,
+      1 => errors.pop(),
+      count => Some(MonitorError::Many {
+        first: Box::new(errors.swap_remove(0)),
+        count
+      })
+    }
+  }
+
+}
+
+impl fmt::Display for MonitorError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      MonitorError::Read { path, source } => {
+        write!(f, "Can't read {}: {source}", path.display())
+      },
+      MonitorError::Parse { path, context } => {
+        write!(f, "Can't parse {}: {context}", path.display())
+      },
+      MonitorError::Many { first, count } => {
+        write!(f, "{first} (and {} more)", count - 1)
+      }
+    }
+  }
+}
+
+impl std::error::Error for MonitorError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      MonitorError::Read { source, .. } => Some(source),
+      MonitorError::Parse { .. } => None,
+      MonitorError::Many { first, .. } => first.source()
+    }
+  }
+}
+
+/// Error of a parser, which doesn't know the path of what it parses. The
+/// reader of the file turns it into a `MonitorError::Parse`.
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+  /// What was being parsed, like `MemTotal` or `uptime`
+  pub context: String
+}
+
+impl ParseError {
+
+  pub fn new(context: &str) -> ParseError {
+    ParseError { context: context.to_string() }
+  }
+
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.context)
+  }
+}
+
+/// Turns a missing or unparsable value into a `ParseError`
+pub trait Context<T> {
+  fn context(self, context: &str) -> Result<T, ParseError>;
+}
+
+impl<T> Context<T> for Option<T> {
+  fn context(self, context: &str) -> Result<T, ParseError> {
+    self.ok_or_else(|| ParseError::new(context))
+  }
+}
+
+impl<T, E> Context<T> for Result<T, E> {
+  fn context(self, context: &str) -> Result<T, ParseError> {
+    self.ok().context(context)
+  }
+}
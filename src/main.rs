@@ -9,10 +9,13 @@ mod process_list;
 mod parsers;
 mod terminal;
 mod battery;
+mod actions;
+mod error;
 
 use std::fs::read_dir;
 use std::collections::HashMap;
 use std::process::exit;
+use std::path::Path;
 
 use parsers::get_cpu_info;
 use parsers::get_vm_stat;
@@ -20,10 +23,23 @@ use proc::*;
 use process_list::*;
 use terminal::{Terminal, Key};
 use crate::battery::Battery;
+use crate::error::MonitorError;
 
 use parsers::get_proc;
+use parsers::get_namespaces;
 use parsers::get_uptime;
 use parsers::get_mem_info;
+use parsers::get_load_avg;
+use parsers::get_pressure;
+use parsers::get_cgroup_stats;
+use parsers::get_system_schedstat;
+use parsers::get_threads;
+use parsers::parse_cpu_list;
+use parsers::get_sensors;
+use parsers::get_filesystems;
+use parsers::get_interrupts;
+use parsers::get_zram;
+use parsers::get_zswap;
 
 /// Returns an Ordering between 2 elements
 fn comp<T: std::cmp::Ord>(a: &T, b: &T) -> std::cmp::Ordering {
@@ -49,71 +65,286 @@ fn count_sort_function(a: &Proc, b: &Proc) -> std::cmp::Ordering {
 }
 
 fn cpu_sort_function(a: &Proc, b: &Proc) -> std::cmp::Ordering {
-  let a_value = a.stat.utime + a.stat.stime;
-  let b_value = b.stat.utime + b.stat.stime;
+  let a_value = a.stat.as_ref().map(|stat| stat.utime + stat.stime);
+  let b_value = b.stat.as_ref().map(|stat| stat.utime + stat.stime);
   comp(&a_value, &b_value)
 }
 
 fn rss_sort_function(a: &Proc, b: &Proc) -> std::cmp::Ordering {
-  let a_value = a.status.vm_rss;
-  let b_value = b.status.vm_rss;
+  let a_value = a.status.as_ref().map(|status| status.vm_rss);
+  let b_value = b.status.as_ref().map(|status| status.vm_rss);
   comp(&a_value, &b_value)
 }
 
 fn swap_sort_function(a: &Proc, b: &Proc) -> std::cmp::Ordering {
-  let a_value = a.status.vm_swap;
-  let b_value = b.status.vm_swap;
+  let a_value = a.status.as_ref().map(|status| status.vm_swap);
+  let b_value = b.status.as_ref().map(|status| status.vm_swap);
   comp(&a_value, &b_value)
 }
 
 fn sum_sort_function(a: &Proc, b: &Proc) -> std::cmp::Ordering {
-  let a_value = a.status.vm_rss + a.status.vm_swap;
-  let b_value = b.status.vm_rss + b.status.vm_swap;
+  let a_value = a.status.as_ref()
+    .map(|status| status.vm_rss + status.vm_swap);
+  let b_value = b.status.as_ref()
+    .map(|status| status.vm_rss + status.vm_swap);
   comp(&a_value, &b_value)
 }
 
-fn io_sort_function(a: &Proc, b: &Proc) -> std::cmp::Ordering {
-  let a_value = a.io.bytes();
-  let b_value = b.io.bytes();
+fn read_sort_function(a: &Proc, b: &Proc) -> std::cmp::Ordering {
+  let a_value = a.io.as_ref().map(|io| io.read(IoMode::Storage));
+  let b_value = b.io.as_ref().map(|io| io.read(IoMode::Storage));
+  comp(&a_value, &b_value)
+}
+
+fn write_sort_function(a: &Proc, b: &Proc) -> std::cmp::Ordering {
+  let a_value = a.io.as_ref().map(|io| io.written(IoMode::Storage));
+  let b_value = b.io.as_ref().map(|io| io.written(IoMode::Storage));
+  comp(&a_value, &b_value)
+}
+
+fn rchar_sort_function(a: &Proc, b: &Proc) -> std::cmp::Ordering {
+  let a_value = a.io.as_ref().map(|io| io.read(IoMode::Logical));
+  let b_value = b.io.as_ref().map(|io| io.read(IoMode::Logical));
+  comp(&a_value, &b_value)
+}
+
+fn wchar_sort_function(a: &Proc, b: &Proc) -> std::cmp::Ordering {
+  let a_value = a.io.as_ref().map(|io| io.written(IoMode::Logical));
+  let b_value = b.io.as_ref().map(|io| io.written(IoMode::Logical));
+  comp(&a_value, &b_value)
+}
+
+fn syscr_sort_function(a: &Proc, b: &Proc) -> std::cmp::Ordering {
+  let a_value = a.io.as_ref().map(|io| io.syscr);
+  let b_value = b.io.as_ref().map(|io| io.syscr);
+  comp(&a_value, &b_value)
+}
+
+fn syscw_sort_function(a: &Proc, b: &Proc) -> std::cmp::Ordering {
+  let a_value = a.io.as_ref().map(|io| io.syscw);
+  let b_value = b.io.as_ref().map(|io| io.syscw);
+  comp(&a_value, &b_value)
+}
+
+fn vcsw_sort_function(a: &Proc, b: &Proc) -> std::cmp::Ordering {
+  let a_value = a.status.as_ref()
+    .map(|status| status.voluntary_ctxt_switches);
+  let b_value = b.status.as_ref()
+    .map(|status| status.voluntary_ctxt_switches);
+  comp(&a_value, &b_value)
+}
+
+fn nvcsw_sort_function(a: &Proc, b: &Proc) -> std::cmp::Ordering {
+  let a_value = a.status.as_ref()
+    .map(|status| status.nonvoluntary_ctxt_switches);
+  let b_value = b.status.as_ref()
+    .map(|status| status.nonvoluntary_ctxt_switches);
+  comp(&a_value, &b_value)
+}
+
+fn minflt_sort_function(a: &Proc, b: &Proc) -> std::cmp::Ordering {
+  let a_value = a.stat.as_ref().map(|stat| stat.minflt);
+  let b_value = b.stat.as_ref().map(|stat| stat.minflt);
+  comp(&a_value, &b_value)
+}
+
+fn majflt_sort_function(a: &Proc, b: &Proc) -> std::cmp::Ordering {
+  let a_value = a.stat.as_ref().map(|stat| stat.majflt);
+  let b_value = b.stat.as_ref().map(|stat| stat.majflt);
+  comp(&a_value, &b_value)
+}
+
+fn rq_wait_sort_function(a: &Proc, b: &Proc) -> std::cmp::Ordering {
+  let a_value = a.schedstat.as_ref().map(|schedstat| schedstat.wait_time);
+  let b_value = b.schedstat.as_ref().map(|schedstat| schedstat.wait_time);
+  comp(&a_value, &b_value)
+}
+
+fn oom_sort_function(a: &Proc, b: &Proc) -> std::cmp::Ordering {
+  let a_value = a.oom_score;
+  let b_value = b.oom_score;
   comp(&a_value, &b_value)
 }
 
 type SortFunction = fn (a: &proc::Proc, b: &proc::Proc) -> std::cmp::Ordering;
 
-static SORT_FUNCTIONS: [SortFunction; 6] = [
+static SORT_FUNCTIONS: [SortFunction; 15] = [
   pid_sort_function,
   cpu_sort_function,
   rss_sort_function,
   swap_sort_function,
   sum_sort_function,
-  io_sort_function
+  read_sort_function,
+  write_sort_function,
+  syscr_sort_function,
+  syscw_sort_function,
+  vcsw_sort_function,
+  nvcsw_sort_function,
+  minflt_sort_function,
+  majflt_sort_function,
+  rq_wait_sort_function,
+  oom_sort_function
 ];
 
-static GROUP_SORT_FUNCTIONS: [SortFunction; 6] = [
+static GROUP_SORT_FUNCTIONS: [SortFunction; 15] = [
   count_sort_function,
   cpu_sort_function,
   rss_sort_function,
   swap_sort_function,
   sum_sort_function,
-  io_sort_function
+  read_sort_function,
+  write_sort_function,
+  syscr_sort_function,
+  syscw_sort_function,
+  vcsw_sort_function,
+  nvcsw_sort_function,
+  minflt_sort_function,
+  majflt_sort_function,
+  rq_wait_sort_function,
+  oom_sort_function
 ];
 
-fn do_reading(
-  terminal: &mut Terminal,
-  process_list: &mut ProcessList,
-  sort_function: SortFunction, group: bool
-) -> Result<(), std::io::Error> {
-  let readed = read_dir("/proc")?;
+fn cgroup_memory_sort_function(
+  a: &CgroupStats, b: &CgroupStats
+) -> std::cmp::Ordering {
+  let a_value = a.memory_current;
+  let b_value = b.memory_current;
+  comp(&a_value, &b_value)
+}
+
+fn cgroup_limit_sort_function(
+  a: &CgroupStats, b: &CgroupStats
+) -> std::cmp::Ordering {
+  let a_value = a.memory_max.unwrap_or(u64::MAX);
+  let b_value = b.memory_max.unwrap_or(u64::MAX);
+  comp(&a_value, &b_value)
+}
+
+fn cgroup_swap_sort_function(
+  a: &CgroupStats, b: &CgroupStats
+) -> std::cmp::Ordering {
+  let a_value = a.memory_swap_current;
+  let b_value = b.memory_swap_current;
+  comp(&a_value, &b_value)
+}
+
+fn cgroup_cpu_sort_function(
+  a: &CgroupStats, b: &CgroupStats
+) -> std::cmp::Ordering {
+  let a_value = a.cpu_usage_usec;
+  let b_value = b.cpu_usage_usec;
+  comp(&a_value, &b_value)
+}
 
-  let procs = readed.filter_map(|read_dir| get_proc(&read_dir.ok()?));
+fn cgroup_throttled_sort_function(
+  a: &CgroupStats, b: &CgroupStats
+) -> std::cmp::Ordering {
+  let a_value = a.cpu_throttled_usec;
+  let b_value = b.cpu_throttled_usec;
+  comp(&a_value, &b_value)
+}
+
+fn cgroup_read_sort_function(
+  a: &CgroupStats, b: &CgroupStats
+) -> std::cmp::Ordering {
+  let a_value = a.io_read_bytes;
+  let b_value = b.io_read_bytes;
+  comp(&a_value, &b_value)
+}
+
+fn cgroup_write_sort_function(
+  a: &CgroupStats, b: &CgroupStats
+) -> std::cmp::Ordering {
+  let a_value = a.io_write_bytes;
+  let b_value = b.io_write_bytes;
+  comp(&a_value, &b_value)
+}
+
+type CgroupSortFunction = fn (
+  a: &CgroupStats, b: &CgroupStats
+) -> std::cmp::Ordering;
+
+/// Sort functions for the cgroup columns, which follow the count when
+/// grouping by cgroup: index `i` of this array is sort index `i + 1`
+static CGROUP_SORT_FUNCTIONS: [CgroupSortFunction; 7] = [
+  cgroup_memory_sort_function,
+  cgroup_limit_sort_function,
+  cgroup_swap_sort_function,
+  cgroup_cpu_sort_function,
+  cgroup_throttled_sort_function,
+  cgroup_read_sort_function,
+  cgroup_write_sort_function
+];
+
+/// Number of sort indexes for `group_by`
+fn sort_function_count(group_by: GroupBy) -> usize {
+  if group_by == GroupBy::Cgroup {
+    CGROUP_SORT_FUNCTIONS.len() + 1
+  } else {
+    SORT_FUNCTIONS.len()
+  }
+}
+
+/// Indexes of the read and write sort functions, which sort by the logical
+/// counters in `IoMode::Logical`
+const READ_SORT_INDEX: usize = 5;
+const WRITE_SORT_INDEX: usize = 6;
+
+/// Reads every process, with its counters relative to the last call. Files of
+/// processes that can't be parsed are reported as one error.
+fn read_processes(
+  terminal: &mut Terminal, process_list: &mut ProcessList
+) -> Result<Vec<Proc>, MonitorError> {
+  let proc_path = Path::new("/proc");
+  let readed = read_dir(proc_path)
+    .map_err(|err| MonitorError::read(proc_path, err))?;
+
+  let own_namespaces = get_namespaces("self");
+  let mut parse_errors = Vec::new();
+  let procs = readed.filter_map(|read_dir| {
+    get_proc(&read_dir.ok()?, own_namespaces.as_ref(), &mut parse_errors)
+  });
 
   let mut procs_vec: Vec<Proc> = procs.collect();
   process_list.on_list(&mut procs_vec);
 
-  if group {
+  if let Some(error) = MonitorError::combine(parse_errors) {
+    terminal.report_error(error);
+  }
+
+  Ok(procs_vec)
+}
+
+/// Prints the process list, returns the selected process or group
+#[allow(clippy::too_many_arguments)]
+fn do_reading(
+  terminal: &mut Terminal,
+  mut procs_vec: Vec<Proc>,
+  cgroup_list: &mut CgroupList,
+  sort_functions: &[SortFunction], sort_function_index: usize,
+  group_by: GroupBy, io_mode: IoMode,
+  container_filter: Option<&str>,
+  selection: &mut Selection
+) -> Option<Proc> {
+  if let Some(container) = container_filter {
+    procs_vec.retain(|proc| proc.container_name().starts_with(container));
+  }
+
+  let restricted = procs_vec.iter()
+    .filter(|proc| proc.restricted && !proc.deleted)
+    .count();
+  if restricted > 0 {
+    terminal.print_restricted(restricted);
+  }
+  terminal.print_header(group_by, io_mode, sort_function_index);
+
+  if group_by.is_group() {
+    // Live processes first, so that groups take the values of one of them
+    procs_vec.sort_by_key(|proc| proc.deleted);
+
     let mut group = HashMap::new();
     for proc in procs_vec {
-      let key = proc.status.name.clone();
+      let key = group_by.key(&proc);
       group.entry(key).and_modify(|p: &mut Proc| {
         *p += proc.clone();
       }).or_insert(proc);
@@ -122,53 +353,368 @@ fn do_reading(
     procs_vec = group.into_iter().map(|e| e.1).collect();
   }
 
-  procs_vec.sort_by(sort_function);
+  let cgroup_stats = if group_by == GroupBy::Cgroup {
+    let stats = procs_vec.iter().filter_map(|proc| {
+      let stats = checked(terminal, get_cgroup_stats(&proc.cgroup))??;
+      Some((proc.cgroup.clone(), stats))
+    }).collect();
+    cgroup_list.on_stats(stats)
+  } else {
+    // Forget old values so rates are right when switching back
+    cgroup_list.on_stats(HashMap::new())
+  };
+
+  match sort_function_index.checked_sub(1) {
+    Some(i) if group_by == GroupBy::Cgroup => {
+      // Cgroups without statistics last
+      let sort_function = CGROUP_SORT_FUNCTIONS[i];
+      procs_vec.sort_by(|a, b| {
+        match (cgroup_stats.get(&a.cgroup), cgroup_stats.get(&b.cgroup)) {
+          (Some(a), Some(b)) => sort_function(&a.value, &b.value),
+          (a, b) => b.is_some().cmp(&a.is_some())
+        }
+      });
+    },
+    _ => procs_vec.sort_by(sort_functions[sort_function_index])
+  }
+
+  let visible = terminal.lines_left();
+  selection.on_list(&procs_vec, group_by, visible);
+
+  let rows = procs_vec.iter().enumerate()
+    .skip(selection.first())
+    .take(visible);
+  for (i, proc) in rows {
+    terminal.print_line(
+      proc, group_by, io_mode, cgroup_stats.get(&proc.cgroup),
+      i == selection.index()
+    );
+  }
+
+  procs_vec.get(selection.index()).cloned()
+}
+
+/// Returns the value of `result`, or shows its error in the diagnostics panel
+fn checked<T>(
+  terminal: &mut Terminal, result: Result<T, MonitorError>
+) -> Option<T> {
+  result.map_err(|err| terminal.report_error(err)).ok()
+}
+
+/// Describes a process or group for prompts and status messages
+fn describe(proc: &Proc) -> String {
+  if proc.pids.len() == 1 {
+    format!("{} ({})", proc.name(), proc.pid)
+  } else {
+    format!("{} processes", proc.pids.len())
+  }
+}
+
+/// The selected process or group, if actions can be applied to it. Rows of
+/// exited processes are refused, their PID may already belong to a new one.
+fn action_target<'a>(
+  terminal: &mut Terminal, selected_proc: Option<&'a Proc>
+) -> Option<&'a Proc> {
+  let proc = selected_proc?;
+  if proc.deleted {
+    terminal.set_status(
+      format!("Can't act on {}: exited since the last refresh", describe(proc))
+    );
+    return None;
+  }
+  Some(proc)
+}
+
+/// Runs `action` for every process of `proc` (more than one for groups), and
+/// shows the outcome in the status line
+fn apply_to_processes(
+  terminal: &mut Terminal, proc: &Proc, description: &str,
+  action: impl Fn(i32) -> Result<(), std::io::Error>
+) {
+  let errors: Vec<(i32, std::io::Error)> = proc.pids.iter()
+    .filter_map(|&pid| action(pid).err().map(|err| (pid, err)))
+    .collect();
+
+  let status = match errors.first() {
+    None => format!("{description}: done for {}", describe(proc)),
+    Some((pid, err)) if errors.len() == 1 => {
+      format!("{description}: failed for PID {pid}: {err}")
+    },
+    Some((pid, err)) => format!(
+      "{description}: failed for {} of {} processes, PID {pid}: {err}",
+      errors.len(), proc.pids.len()
+    )
+  };
+
+  terminal.set_status(status);
+}
+
+/// Runs `action` for every thread of `pid`, or for `pid` alone if its threads
+/// can't be listed. Threads that exit meanwhile (`ESRCH`) are skipped, the
+/// others are all tried and their failures combined into one error.
+fn for_each_thread(
+  pid: i32, action: impl Fn(i32) -> Result<(), std::io::Error>
+) -> Result<(), std::io::Error> {
+  let threads = get_threads(pid);
+  if threads.is_empty() {
+    return action(pid);
+  }
+
+  let mut exited = 0;
+  let mut errors = Vec::new();
+  for &tid in &threads {
+    match action(tid) {
+      Ok(()) => {},
+      Err(err) if err.raw_os_error() == Some(libc::ESRCH) => exited += 1,
+      Err(err) => errors.push(err)
+    }
+  }
 
-  for proc in procs_vec.iter() {
-    terminal.print_line(proc, group);
+  if exited == threads.len() {
+    return Err(std::io::Error::from_raw_os_error(libc::ESRCH));
   }
+  match errors.len() {
+    0 => Ok(()),
+    1 if threads.len() == 1 => Err(errors.remove(0)),
+    failed => Err(std::io::Error::new(
+      errors[0].kind(),
+      format!("{} (for {failed} of {} threads)", errors[0], threads.len())
+    ))
+  }
+}
+
+/// Asks for a new nice value for the selected process or group and sets it
+fn edit_nice(terminal: &mut Terminal, proc: &Proc) {
+  let target = describe(proc);
+
+  let current = proc.stat.as_ref()
+    .map_or("-".to_string(), |stat| stat.nice.to_string());
+  let message = format!(
+    "Nice value for {target}, currently {current} [-20..19]: "
+  );
+  let Some(input) = terminal.prompt(&message) else { return; };
+
+  let nice = match input.trim().parse::<i32>() {
+    Ok(nice) if actions::NICE_RANGE.contains(&nice) => nice,
+    _ => {
+      terminal.set_status(format!("Invalid nice value: {input}"));
+      return;
+    }
+  };
+
+  if !terminal.confirm(&format!("Set nice {nice} for {target}?")) { return; }
+
+  apply_to_processes(
+    terminal, proc, &format!("Nice {nice}"),
+    |pid| for_each_thread(pid, |tid| actions::set_nice(tid, nice))
+  );
+}
+
+/// Asks for a new scheduling policy for the selected process or group and
+/// sets it
+fn edit_policy(terminal: &mut Terminal, proc: &Proc) {
+  let target = describe(proc);
+
+  let message = format!(
+    "Policy for {target}, currently {} [o]ther [b]atch [i]dle: ",
+    proc.stat.as_ref().map_or("-", |stat| stat.policy_name())
+  );
+  let Some(input) = terminal.prompt(&message) else { return; };
+
+  let (policy, policy_name) = match input.trim() {
+    "o" | "other" => (libc::SCHED_OTHER, "OTHER"),
+    "b" | "batch" => (libc::SCHED_BATCH, "BATCH"),
+    "i" | "idle" => (libc::SCHED_IDLE, "IDLE"),
+    _ => {
+      terminal.set_status(format!("Invalid policy: {input}"));
+      return;
+    }
+  };
+
+  let message = format!("Set policy {policy_name} for {target}?");
+  if !terminal.confirm(&message) { return; }
+
+  apply_to_processes(
+    terminal, proc, &format!("Policy {policy_name}"),
+    |pid| for_each_thread(pid, |tid| actions::set_policy(tid, policy))
+  );
+}
 
-  Result::Ok(())
+/// Asks for the CPUs the selected process or group may run on, and optionally
+/// applies them to all their threads
+fn edit_affinity(terminal: &mut Terminal, proc: &Proc) {
+  let target = describe(proc);
+
+  let message = format!(
+    "CPUs for {target}, currently {} (like 0-3,8): ",
+    proc.status.as_ref().map_or("-", |status| &status.cpus_allowed_list)
+  );
+  let Some(input) = terminal.prompt(&message) else { return; };
+
+  let Some(cpus) = parse_cpu_list(&input) else {
+    terminal.set_status(format!("Invalid CPU list: {input}"));
+    return;
+  };
+
+  let message = format!("Set CPUs {} for {target}?", input.trim());
+  if !terminal.confirm(&message) { return; }
+  let all_threads = terminal.confirm("Apply to all their threads too?");
+
+  apply_to_processes(
+    terminal, proc, &format!("CPUs {}", input.trim()),
+    |pid| if all_threads {
+      for_each_thread(pid, |tid| actions::set_affinity(tid, &cpus))
+    } else {
+      actions::set_affinity(pid, &cpus)
+    }
+  );
+}
+
+/// Asks for a signal and sends it to the selected process or every process of
+/// the selected group
+fn send_signal(terminal: &mut Terminal, proc: &Proc) {
+  let target = describe(proc);
+
+  let names: Vec<&str> = actions::SIGNALS.iter().map(|(name, _)| *name).collect();
+  let message = format!(
+    "Signal for {target} ({} or number): ", names.join(" ")
+  );
+  let Some(input) = terminal.prompt(&message) else { return; };
+
+  let Some(signal) = actions::parse_signal(&input) else {
+    terminal.set_status(format!("Invalid signal: {input}"));
+    return;
+  };
+
+  let signal_name = input.trim().to_ascii_uppercase();
+  let message = format!("Send {signal_name} to {target}?");
+  if !terminal.confirm(&message) { return; }
+
+  apply_to_processes(
+    terminal, proc, &format!("Signal {signal_name}"),
+    |pid| actions::send_signal(pid, signal)
+  );
+}
+
+/// Asks for a new `oom_score_adj` for the selected process and sets it
+fn edit_oom_score_adj(terminal: &mut Terminal, proc: &Proc) {
+  let name = proc.name();
+  let pid = proc.pid;
+
+  let current = proc.oom_score_adj
+    .map_or("-".to_string(), |oom_score_adj| oom_score_adj.to_string());
+  let message = format!(
+    "oom_score_adj for {name} ({pid}), currently {current}: "
+  );
+  let Some(input) = terminal.prompt(&message) else { return; };
+
+  let value = match input.trim().parse::<i32>() {
+    Ok(value) if actions::OOM_SCORE_ADJ_RANGE.contains(&value) => value,
+    _ => {
+      terminal.set_status(format!("Invalid oom_score_adj: {input}"));
+      return;
+    }
+  };
+
+  let message = format!("Set oom_score_adj of {name} ({pid}) to {value}?");
+  if !terminal.confirm(&message) { return; }
+
+  match actions::set_oom_score_adj(pid, value) {
+    Ok(()) => terminal.set_status(
+      format!("oom_score_adj of {name} ({pid}) set to {value}")
+    ),
+    Err(err) => terminal.set_status(
+      format!("Can't set oom_score_adj of {name} ({pid}): {err}")
+    )
+  }
+}
+
+/// What is shown below the system information
+#[derive(Clone, Copy, PartialEq)]
+enum Screen {
+  Processes,
+  Disks,
+  Interrupts
 }
 
 fn main() {
-  let delay_secs = parse_arguments();
+  let arguments = parse_arguments();
 
-  let mut terminal = Terminal::init(delay_secs);
+  let mut terminal = Terminal::init(arguments.delay_secs);
   let mut battery = Battery::init();
 
   let mut process_list = ProcessList::new();
+  let mut cgroup_list = CgroupList::new();
 
   let mut sort_function_index: usize = 1;
-  let mut group: bool = false;
+  let mut group_by = GroupBy::None;
+  let mut io_mode = IoMode::Storage;
+  let mut container_filter: Option<String> = None;
+  let mut selection = Selection::new();
+  let mut memory_expanded = false;
+  let mut screen = Screen::Processes;
+  let mut last_interrupts = None;
   let mut last_uptime = Uptime::default();
-  let mut uptime: Uptime;
-  let mut last_vmstat = get_vm_stat();
-  let mut vmstat: VmStat;
+  let mut last_vmstat = get_vm_stat().ok();
+  let mut last_pressure = get_pressure().ok().flatten();
+  let mut last_schedstat = get_system_schedstat().ok().flatten();
 
   loop {
     let sort_functions = {
-      if group {
+      let mut sort_functions = if group_by.is_group() {
         GROUP_SORT_FUNCTIONS
       } else {
         SORT_FUNCTIONS
+      };
+      if io_mode == IoMode::Logical {
+        sort_functions[READ_SORT_INDEX] = rchar_sort_function;
+        sort_functions[WRITE_SORT_INDEX] = wchar_sort_function;
       }
+      sort_functions
     };
 
     terminal.clear();
     terminal.update_time();
-    uptime = get_uptime();
-    terminal.print_uptime(&uptime, &last_uptime);
-    last_uptime = uptime;
-    terminal.print_mem_info(&get_mem_info());
-    if let Some(cpu_info) = get_cpu_info() {
+    if let Some(uptime) = checked(&mut terminal, get_uptime()) {
+      let load_avg = checked(&mut terminal, get_load_avg());
+      terminal.print_uptime(&uptime, &last_uptime, load_avg.as_ref());
+      last_uptime = uptime;
+    }
+    if let Some(mem_info) = checked(&mut terminal, get_mem_info()) {
+      terminal.print_mem_info(&mem_info, memory_expanded);
+      let zram = checked(&mut terminal, get_zram(Path::new("/sys"))).flatten();
+      terminal.print_compressed_swap(
+        zram.as_ref(),
+        get_zswap(Path::new("/sys"), &mem_info, util::page_size()).as_ref()
+      );
+    }
+    let cpu_info = checked(&mut terminal, get_cpu_info())
+      .filter(|cpu_info| !cpu_info.is_empty());
+    if let Some(cpu_info) = cpu_info {
       terminal.print_cpu_speed(&cpu_info);
     }
-    vmstat = get_vm_stat();
-    let swap_stats = &vmstat - &last_vmstat;
-    terminal.print_swap_stats(swap_stats.pswpin, swap_stats.pswpout);
+    terminal.print_sensors(&get_sensors(Path::new("/sys")));
+
+    let vmstat = checked(&mut terminal, get_vm_stat());
+    if let (Some(vmstat), Some(last)) = (&vmstat, &last_vmstat) {
+      let vmstat_delta = vmstat - last;
+      terminal.print_swap_stats(&vmstat_delta);
+      terminal.print_paging(&vmstat_delta);
+    }
     last_vmstat = vmstat;
 
+    let pressure = checked(&mut terminal, get_pressure()).flatten();
+    if let (Some(pressure), Some(last)) = (&pressure, &last_pressure) {
+      terminal.print_pressure(pressure, last);
+    }
+    last_pressure = pressure;
+
+    let schedstat = checked(&mut terminal, get_system_schedstat()).flatten();
+    if let (Some(schedstat), Some(last)) = (&schedstat, &last_schedstat) {
+      terminal.print_system_schedstat(&(schedstat - last));
+    }
+    last_schedstat = schedstat;
+
     if let Some(battery) = &mut battery {
       battery.refresh();
       if battery.discharging() {
@@ -182,19 +728,54 @@ fn main() {
       }
     }
 
-    terminal.print_header(group, sort_function_index);
-    let result = do_reading(
-      &mut terminal, &mut process_list,
-      sort_functions[sort_function_index], group
-    );
-    if let Err(err) = result { println!("{err}"); }
+    // Processes are read on every screen, so that their rates are relative to
+    // the last refresh when switching back. Cgroups are only read while shown,
+    // their last values are forgotten.
+    let procs = read_processes(&mut terminal, &mut process_list);
+    let procs = checked(&mut terminal, procs);
+    if screen != Screen::Processes {
+      cgroup_list.on_stats(HashMap::new());
+    }
+
+    // Actions only apply to the process selected in the current list
+    let mut selected_proc = None;
+
+    match screen {
+      Screen::Processes => {
+        if let Some(container) = &container_filter {
+          terminal.print_filter(container);
+        }
+
+        if let Some(procs) = procs {
+          selected_proc = do_reading(
+            &mut terminal, procs, &mut cgroup_list,
+            &sort_functions, sort_function_index, group_by, io_mode,
+            container_filter.as_deref(), &mut selection
+          );
+        }
+      },
+      Screen::Disks => {
+        if let Some(filesystems) = checked(&mut terminal, get_filesystems()) {
+          terminal.print_filesystems(
+            &filesystems, arguments.disk_warning, arguments.disk_critical
+          );
+        }
+      },
+      Screen::Interrupts => {
+        if let Some(interrupts) = checked(&mut terminal, get_interrupts()) {
+          let last = last_interrupts.as_ref().unwrap_or(&interrupts);
+          terminal.print_interrupts(&(&interrupts - last));
+          last_interrupts = Some(interrupts);
+        }
+      }
+    }
     terminal.refresh();
 
     let key_option = terminal.wait_key();
     match key_option {
       Some(Key::Right) => {
         sort_function_index += 1;
-        if sort_function_index >= sort_functions.len() {
+        if sort_function_index >= sort_function_count(group_by) {
           sort_function_index = 0;
         }
       },
@@ -202,10 +783,75 @@ fn main() {
         if sort_function_index > 0 {
           sort_function_index -= 1;
         } else {
-          sort_function_index = sort_functions.len() - 1;
+          sort_function_index = sort_function_count(group_by) - 1;
+        }
+      },
+      Some(Key::Up) => selection.up(),
+      Some(Key::Down) => selection.down(),
+      Some(Key::Group) => {
+        group_by = group_by.next();
+        if sort_function_index >= sort_function_count(group_by) {
+          sort_function_index = 1;
+        }
+      },
+      Some(Key::IoMode) => io_mode = io_mode.next(),
+      Some(Key::Filter) => {
+        let message = "Container ID, \"host\" or empty for all: ";
+        if let Some(container) = terminal.prompt(message) {
+          container_filter = Some(container).filter(|c| !c.is_empty());
         }
       },
-      Some(Key::Group) => group = !group,
+      Some(Key::OomScoreAdj) => {
+        match action_target(&mut terminal, selected_proc.as_ref()) {
+          Some(proc) if !group_by.is_group() => {
+            edit_oom_score_adj(&mut terminal, proc);
+          },
+          Some(_) => terminal.set_status(
+            "oom_score_adj can only be set for a single process".to_string()
+          ),
+          None => {}
+        }
+      },
+      Some(Key::Nice) => {
+        if let Some(proc) = action_target(&mut terminal, selected_proc.as_ref()) {
+          edit_nice(&mut terminal, proc);
+        }
+      },
+      Some(Key::Policy) => {
+        if let Some(proc) = action_target(&mut terminal, selected_proc.as_ref()) {
+          edit_policy(&mut terminal, proc);
+        }
+      },
+      Some(Key::Affinity) => {
+        if let Some(proc) = action_target(&mut terminal, selected_proc.as_ref()) {
+          edit_affinity(&mut terminal, proc);
+        }
+      },
+      Some(Key::Kill) => {
+        if let Some(proc) = action_target(&mut terminal, selected_proc.as_ref()) {
+          send_signal(&mut terminal, proc);
+        }
+      },
+      Some(Key::ScrollLeft) => match screen {
+        Screen::Interrupts => terminal.scroll_cpus_left(),
+        _ => terminal.scroll_left()
+      },
+      Some(Key::ScrollRight) => match screen {
+        Screen::Interrupts => terminal.scroll_cpus_right(),
+        _ => terminal.scroll_right()
+      },
+      Some(Key::Memory) => memory_expanded = !memory_expanded,
+      Some(Key::Disks) => screen = match screen {
+        Screen::Disks => Screen::Processes,
+        _ => Screen::Disks
+      },
+      Some(Key::Interrupts) => {
+        screen = match screen {
+          Screen::Interrupts => Screen::Processes,
+          _ => Screen::Interrupts
+        };
+        last_interrupts = None;
+      },
       Some(Key::Esc) => break,
       _ => ()
     }
@@ -214,35 +860,65 @@ fn main() {
   terminal.deinit(); // TODO: Make sure this gets called
 }
 
-fn parse_arguments() -> u16 {
-  let mut args = std::env::args();
-  let arg_1 = args.nth(1);
-  let arg_2 = args.next();
+struct Arguments {
+  delay_secs: u16,
+  /// Disk usage percentage from which filesystems are shown in yellow
+  disk_warning: f32,
+  /// Disk usage percentage from which filesystems are shown in red
+  disk_critical: f32
+}
 
-  if args.next().is_some() {
-    println!("Too many arguments.");
-    exit(1);
-  }
+fn parse_arguments() -> Arguments {
+  let mut arguments = Arguments {
+    delay_secs: 2,
+    disk_warning: 80f32,
+    disk_critical: 90f32
+  };
 
-  let delay_secs;
+  let mut args = std::env::args().skip(1);
 
-  match (arg_1, arg_2) {
-    (None, None) => delay_secs = 2,
+  while let Some(arg) = args.next() {
+    let Some(value) = args.next() else {
+      println!("Bad arguments.");
+      exit(1);
+    };
 
-    (Some(arg_1), Some(arg_2)) if arg_1 == "-d" => {
-      if let Ok(delay) = arg_2.parse() {
-        delay_secs = delay;
-      } else {
-        println!("Invalid delay seconds.");
+    match arg.as_str() {
+      "-d" => {
+        if let Ok(delay) = value.parse() {
+          arguments.delay_secs = delay;
+        } else {
+          println!("Invalid delay seconds.");
+          exit(1);
+        }
+      },
+
+      "-w" | "-c" => {
+        let threshold = match value.parse::<f32>() {
+          Ok(threshold) if threshold >= 0f32 && threshold <= 100f32 => threshold,
+          _ => {
+            println!("Invalid disk usage percentage.");
+            exit(1);
+          }
+        };
+        if arg == "-w" {
+          arguments.disk_warning = threshold;
+        } else {
+          arguments.disk_critical = threshold;
+        }
+      },
+
+      _ => {
+        println!("Bad arguments.");
         exit(1);
       }
     }
+  }
 
-    _ => {
-      println!("Bad arguments.");
-      exit(1);
-    }
+  if arguments.disk_warning > arguments.disk_critical {
+    println!("The warning disk usage can't be higher than the critical one.");
+    exit(1);
   }
 
-  delay_secs
+  arguments
 }
@@ -3,19 +3,44 @@
 #[cfg(test)]
 mod tests;
 
+use std::collections::HashMap;
 use std::path::Path;
+use std::path::PathBuf;
 use std::fs::DirEntry;
-use std::fs::read_to_string;
+use std::fs::read_link;
+use std::fs::read_dir;
+use std::io::ErrorKind;
 
 use std::str::FromStr;
 
+use crate::actions::MAX_CPUS;
+use crate::error::Context;
+use crate::error::MonitorError;
+use crate::error::ParseError;
+
 use super::proc::Status;
 use super::proc::Proc;
 use super::proc::Uptime;
 use super::proc::MemInfo;
+use super::proc::LoadAvg;
+use super::proc::Pressure;
+use super::proc::PressureLine;
+use super::proc::PressureInfo;
 use super::proc::VmStat;
 use super::proc::Stat;
 use super::proc::IoStats;
+use super::proc::CgroupStats;
+use super::proc::SchedStat;
+use super::proc::SystemSchedStat;
+use super::proc::Sensor;
+use super::proc::Mount;
+use super::proc::Filesystem;
+use super::proc::Interrupt;
+use super::proc::Interrupts;
+use super::proc::CompressedSwap;
+use super::proc::SensorKind;
+use super::proc::Namespaces;
+use super::proc::Resets;
 use crate::proc::CpuInfo;
 
 fn get_value(name: &str, line: &str) -> Option<u64> {
@@ -27,6 +52,13 @@ fn get_value(name: &str, line: &str) -> Option<u64> {
   u64::from_str(value).ok()
 }
 
+/// Like `get_value`, but a line of `name` without a number is an error
+fn get_number(name: &str, line: &str) -> Result<Option<u64>, ParseError> {
+  if !line.starts_with(name) { return Ok(None); }
+
+  get_value(name, line).context(name.trim_end_matches(':')).map(Some)
+}
+
 fn get_value_str(name: &str, line: &str) -> Option<String> {
   if !line.starts_with(name) { return None; }
 
@@ -40,7 +72,12 @@ fn get_value_str(name: &str, line: &str) -> Option<String> {
   Some(value_str)
 }
 
-fn parse_status(file_content: &str) -> Option<Status> {
+/// Converts a value in kB, as most of /proc reports sizes, to bytes
+fn kb_to_bytes(value: u64, context: &str) -> Result<u64, ParseError> {
+  value.checked_mul(1024).context(context)
+}
+
+fn parse_status(file_content: &str) -> Result<Status, ParseError> {
   let mut lines = file_content.split('\n');
 
   let mut vm_peack = 0;
@@ -58,56 +95,68 @@ fn parse_status(file_content: &str) -> Option<Status> {
   let mut vm_lib = 0;
   let mut vm_pte = 0;
   let mut vm_swap = 0;
+  let mut voluntary_ctxt_switches = 0;
+  let mut nonvoluntary_ctxt_switches = 0;
+  let mut cpus_allowed_list = String::new();
 
 
   let first_line = lines.next().unwrap_or("");
-  let name = get_value_str("Name:", first_line)?;
+  let name = get_value_str("Name:", first_line).context("Name")?;
 
   for line in lines {
-    if let Some(value) = get_value("VmPeak:", line) {
-      vm_peack = value * 1024;
+    if let Some(value) = get_number("VmPeak:", line)? {
+      vm_peack = kb_to_bytes(value, "VmPeak")?;
+    }
+    else if let Some(value) = get_number("VmSize:", line)? {
+      vm_size = kb_to_bytes(value, "VmSize")?;
+    }
+    else if let Some(value) = get_number("VmLck:", line)? {
+      vm_lck = kb_to_bytes(value, "VmLck")?;
     }
-    else if let Some(value) = get_value("VmSize:", line) {
-      vm_size = value * 1024;
+    else if let Some(value) = get_number("VmPin:", line)? {
+      vm_pin = kb_to_bytes(value, "VmPin")?;
     }
-    else if let Some(value) = get_value("VmLck:", line) {
-      vm_lck = value * 1024;
+    else if let Some(value) = get_number("VmHWM:", line)? {
+      vm_hwm = kb_to_bytes(value, "VmHWM")?;
     }
-    else if let Some(value) = get_value("VmPin:", line) {
-      vm_pin = value * 1024;
+    else if let Some(value) = get_number("VmRSS:", line)? {
+      vm_rss = kb_to_bytes(value, "VmRSS")?;
     }
-    else if let Some(value) = get_value("VmHWM:", line) {
-      vm_hwm = value * 1024;
+    else if let Some(value) = get_number("RssAnon:", line)? {
+      rss_anon = kb_to_bytes(value, "RssAnon")?;
     }
-    else if let Some(value) = get_value("VmRSS:", line) {
-      vm_rss = value * 1024;
+    else if let Some(value) = get_number("RssFile:", line)? {
+      rss_file = kb_to_bytes(value, "RssFile")?;
     }
-    else if let Some(value) = get_value("RssAnon:", line) {
-      rss_anon = value * 1024;
+    else if let Some(value) = get_number("RssShmem:", line)? {
+      rss_shmem = kb_to_bytes(value, "RssShmem")?;
     }
-    else if let Some(value) = get_value("RssFile:", line) {
-      rss_file = value * 1024;
+    else if let Some(value) = get_number("VmData:", line)? {
+      vm_data = kb_to_bytes(value, "VmData")?;
     }
-    else if let Some(value) = get_value("RssShmem:", line) {
-      rss_shmem = value * 1024;
+    else if let Some(value) = get_number("VmStk:", line)? {
+      vm_stk = kb_to_bytes(value, "VmStk")?;
     }
-    else if let Some(value) = get_value("VmData:", line) {
-      vm_data = value * 1024;
+    else if let Some(value) = get_number("VmExe:", line)? {
+      vm_exe = kb_to_bytes(value, "VmExe")?;
     }
-    else if let Some(value) = get_value("VmStk:", line) {
-      vm_stk = value * 1024;
+    else if let Some(value) = get_number("VmLib:", line)? {
+      vm_lib = kb_to_bytes(value, "VmLib")?;
     }
-    else if let Some(value) = get_value("VmExe:", line) {
-      vm_exe = value * 1024;
+    else if let Some(value) = get_number("VmPTE:", line)? {
+      vm_pte = kb_to_bytes(value, "VmPTE")?;
     }
-    else if let Some(value) = get_value("VmLib:", line) {
-      vm_lib = value * 1024;
+    else if let Some(value) = get_number("VmSwap:", line)? {
+      vm_swap = kb_to_bytes(value, "VmSwap")?;
     }
-    else if let Some(value) = get_value("VmPTE:", line) {
-      vm_pte = value * 1024;
+    else if let Some(value) = get_number("voluntary_ctxt_switches:", line)? {
+      voluntary_ctxt_switches = value;
     }
-    else if let Some(value) = get_value("VmSwap:", line) {
-      vm_swap = value * 1024;
+    else if let Some(value) = get_number("nonvoluntary_ctxt_switches:", line)? {
+      nonvoluntary_ctxt_switches = value;
+    }
+    else if let Some(value) = get_value_str("Cpus_allowed_list:", line) {
+      cpus_allowed_list = value;
     }
   }
 
@@ -127,75 +176,276 @@ fn parse_status(file_content: &str) -> Option<Status> {
     vm_exe,
     vm_lib,
     vm_pte,
-    vm_swap
+    vm_swap,
+    voluntary_ctxt_switches,
+    nonvoluntary_ctxt_switches,
+    cpus_allowed_list
   };
 
-  Some(result)
+  Ok(result)
+}
+
+/// Parses field `number` of a stat file, numbered as in man proc(5), `fields`
+/// starting from (3) state
+fn get_stat_field<T: FromStr>(fields: &[&str], number: usize) -> Option<T> {
+  fields.get(number - 3)?.parse().ok()
 }
 
-fn parse_stat(file_content: &str) -> Option<Stat> {
-  // Name is the second element
-  // TODO: in case of error, display something
-  let contents = file_content.split(')').nth(1)?.split_whitespace();
+fn parse_stat(file_content: &str) -> Result<Stat, ParseError> {
+  // Name is the second element, between parentheses. It may contain
+  // parentheses too, so the fields start after the last one.
+  let fields: Vec<&str> = file_content.rsplit_once(')').context("comm")?.1
+    .split_whitespace()
+    .collect();
 
-  let mut contents = contents.skip(11);
-  Some(Stat {
-    utime: contents.next()?.parse().ok()?,
-    stime: contents.next()?.parse().ok()?
+  Ok(Stat {
+    minflt: get_stat_field(&fields, 10).context("minflt")?,
+    majflt: get_stat_field(&fields, 12).context("majflt")?,
+    utime: get_stat_field(&fields, 14).context("utime")?,
+    stime: get_stat_field(&fields, 15).context("stime")?,
+    nice: get_stat_field(&fields, 19).context("nice")?,
+    starttime: get_stat_field(&fields, 22).context("starttime")?,
+    processor: get_stat_field(&fields, 39).unwrap_or(0),
+    policy: get_stat_field(&fields, 41).unwrap_or(0)
   })
 }
 
-fn parse_io(file_content: &str) -> Option<IoStats> {
+fn parse_io(file_content: &str) -> Result<IoStats, ParseError> {
   let mut io_stats = IoStats::default();
   let lines = file_content.split('\n');
 
+  // Some kernels leave out the storage fields, but rchar is always there
+  if !file_content.starts_with("rchar:") {
+    return Err(ParseError::new("rchar"));
+  }
+
   for line in lines {
-    if let Some(value) = get_value("rchar:", line) {
+    if let Some(value) = get_number("rchar:", line)? {
       io_stats.rchar = value;
     }
-    else if let Some(value) = get_value("wchar:", line) {
+    else if let Some(value) = get_number("wchar:", line)? {
       io_stats.wchar = value;
     }
-    else if let Some(value) = get_value("syscr:", line) {
+    else if let Some(value) = get_number("syscr:", line)? {
       io_stats.syscr = value;
     }
-    else if let Some(value) = get_value("syscw:", line) {
+    else if let Some(value) = get_number("syscw:", line)? {
       io_stats.syscw = value;
     }
-    else if let Some(value) = get_value("read_bytes:", line) {
+    else if let Some(value) = get_number("read_bytes:", line)? {
       io_stats.read_bytes = value;
     }
-    else if let Some(value) = get_value("write_bytes:", line) {
+    else if let Some(value) = get_number("write_bytes:", line)? {
       io_stats.write_bytes = value;
     }
-    else if let Some(value) = get_value("cancelled_write_bytes:", line) {
+    else if let Some(value) = get_number("cancelled_write_bytes:", line)? {
       io_stats.cancelled_write_bytes = value;
     }
   }
 
-  Some(io_stats)
+  Ok(io_stats)
+}
+
+fn parse_schedstat(file_content: &str) -> Result<SchedStat, ParseError> {
+  let mut parts = file_content.split_whitespace();
+  let mut next = |context| {
+    parts.next().and_then(|part| part.parse().ok()).context(context)
+  };
+
+  Ok(SchedStat {
+    run_time: next("run_time")?,
+    wait_time: next("wait_time")?,
+    timeslices: next("timeslices")?
+  })
+}
+
+/// Reads a file that may not be valid UTF-8, like a status whose `Name` is
+/// arbitrary bytes or a mountinfo with such paths. Invalid sequences are
+/// replaced rather than making the whole file unreadable.
+fn read_lossy(path: &Path) -> std::io::Result<String> {
+  let bytes = std::fs::read(path)?;
+  Ok(String::from_utf8(bytes)
+    .unwrap_or_else(|err| String::from_utf8_lossy(err.as_bytes()).into_owned()))
+}
+
+/// Returns the cgroup v2 path from the `0::<path>` line of
+/// `/proc/<pid>/cgroup`
+fn parse_cgroup(file_content: &str) -> Option<String> {
+  file_content.lines()
+    .find_map(|line| line.strip_prefix("0::"))
+    .map(|path| path.to_string())
+}
+
+/// Markers container runtimes leave in the cgroup path of their containers
+const CONTAINER_MARKERS: [&str; 5] = [
+  "docker", "containerd", "crio", "libpod", "kubepods"
+];
+
+/// Prefixes container runtimes use for the systemd scope of a container
+const CONTAINER_SCOPE_PREFIXES: [&str; 5] = [
+  "docker-", "cri-containerd-", "containerd-", "crio-", "libpod-"
+];
+
+/// Extracts the short (12 characters) container ID from a cgroup v2 path
+/// created by docker, containerd, CRI-O, podman or kubernetes, both with the
+/// systemd and the cgroupfs drivers, for example:
+///
+/// - `/system.slice/docker-<id>.scope`
+/// - `/kubepods/burstable/pod<uid>/<id>`
+/// - `/machine.slice/libpod-<id>.scope/container`
+fn parse_container_id(cgroup: &str) -> Option<String> {
+  if !CONTAINER_MARKERS.iter().any(|marker| cgroup.contains(marker)) {
+    return None;
+  }
+
+  cgroup.split('/').rev().find_map(|component| {
+    let component = component.trim_end_matches(".scope");
+    let id = CONTAINER_SCOPE_PREFIXES.iter()
+      .find_map(|prefix| component.strip_prefix(prefix))
+      .unwrap_or(component);
+
+    let is_id = id.len() == 64 && id.chars().all(|c| c.is_ascii_hexdigit());
+    if is_id { Some(id[..12].to_string()) } else { None }
+  })
+}
+
+/// Suffixes of the systemd units that can own a cgroup
+const SYSTEMD_UNIT_SUFFIXES: [&str; 3] = [".service", ".scope", ".slice"];
+
+/// Returns the deepest systemd unit in a cgroup v2 path, like `foo.service`
+/// for `/system.slice/foo.service` or `/system.slice/foo.service/worker`
+fn parse_systemd_unit(cgroup: &str) -> Option<String> {
+  if cgroup == "/" {
+    return Some("-.slice".to_string());
+  }
+
+  cgroup.split('/').rev()
+    .find(|component| {
+      SYSTEMD_UNIT_SUFFIXES.iter().any(|suffix| component.ends_with(suffix))
+    })
+    .map(|unit| unit.to_string())
+}
+
+/// Parses the inode from a namespace link like `pid:[4026531836]`
+fn parse_namespace_inode(link: &str) -> Option<u64> {
+  link.split_once(":[")?.1.strip_suffix(']')?.parse().ok()
+}
+
+fn get_namespace(pid: &str, namespace: &str) -> Option<u64> {
+  let link = read_link(format!("/proc/{pid}/ns/{namespace}")).ok()?;
+  parse_namespace_inode(link.to_str()?)
 }
 
-pub fn get_proc(entry: &DirEntry) -> Option<Proc> {
+/// Namespaces of a process, `self` for the monitor's own
+pub fn get_namespaces(pid: &str) -> Option<Namespaces> {
+  Some(Namespaces {
+    pid: get_namespace(pid, "pid")?,
+    mnt: get_namespace(pid, "mnt")?,
+    uts: get_namespace(pid, "uts")?
+  })
+}
+
+/// Cgroup markers of the runtimes that only isolate with namespaces, like
+/// LXC or systemd-nspawn
+const NAMESPACE_CONTAINER_MARKERS: [&str; 2] = ["machine.slice", "lxc"];
+
+/// Whether a process with its own PID namespace is a container. Sandboxes
+/// like the Chrome renderers or Flatpak apps have one too, but they share our
+/// hostname: containers also have their own mount and UTS namespaces, or a
+/// cgroup of a container runtime.
+fn is_namespace_container(
+  namespaces: &Namespaces, own: &Namespaces, cgroup: &str
+) -> bool {
+  if namespaces.pid == own.pid {
+    return false;
+  }
+
+  let isolated = namespaces.mnt != own.mnt && namespaces.uts != own.uts;
+  let runtime = NAMESPACE_CONTAINER_MARKERS.iter()
+    .any(|marker| cgroup.contains(marker));
+  isolated || runtime
+}
+
+/// Container of a process not found by its cgroup, but isolated by namespaces
+/// (like LXC or unknown runtimes)
+fn get_namespace_container(
+  pid: &str, cgroup: &str, own: &Namespaces
+) -> Option<String> {
+  let namespaces = get_namespaces(pid)?;
+  if is_namespace_container(&namespaces, own, cgroup) {
+    Some(format!("ns-{}", namespaces.pid))
+  } else {
+    None
+  }
+}
+
+/// Parses `content`, read from /proc/<pid>/`file`. Failures are added to
+/// `errors` and leave the value out.
+fn parse_proc_file<T>(
+  pid: &str, file: &str, content: Option<String>,
+  parse: impl FnOnce(&str) -> Result<T, ParseError>,
+  errors: &mut Vec<MonitorError>
+) -> Option<T> {
+  parse(&content?).map_err(|err| {
+    let path = format!("/proc/{pid}/{file}");
+    errors.push(MonitorError::parse(Path::new(&path), err));
+  }).ok()
+}
+
+/// Reads a process, `own_namespaces` are the monitor's, to tell the processes
+/// in containers. Files that can't be parsed are added to `parse_errors`.
+pub fn get_proc(
+  entry: &DirEntry, own_namespaces: Option<&Namespaces>,
+  parse_errors: &mut Vec<MonitorError>
+) -> Option<Proc> {
 
   let name = entry.file_name().into_string().ok()?;
   let pid = name.parse::<i32>().ok()?;
 
-  let cmd_line_path = format!("/proc/{name}/cmdline");
-  let cmdline = read_to_string(cmd_line_path).ok()?;
+  // Files that can't be read are left out, but the process is still listed
+  let mut restricted = false;
+  let mut read = |file: &str| {
+    match read_lossy(Path::new(&format!("/proc/{name}/{file}"))) {
+      Ok(content) => Some(content),
+      Err(err) => {
+        restricted = restricted || err.kind() == ErrorKind::PermissionDenied;
+        None
+      }
+    }
+  };
 
-  let status_path = format!("/proc/{name}/status");
-  let status_string = read_to_string(status_path).ok()?;
+  let cmdline = read("cmdline");
+  let status = parse_proc_file(
+    &name, "status", read("status"), parse_status, parse_errors
+  );
+  let stat = parse_proc_file(
+    &name, "stat", read("stat"), parse_stat, parse_errors
+  );
+  let io = parse_proc_file(&name, "io", read("io"), parse_io, parse_errors);
 
-  let stat_path = format!("/proc/{name}/stat");
-  let stat_string = read_to_string(stat_path).ok()?;
+  // Not every kernel has cgroups v2, this is not a reason to hide the process
+  let cgroup = read("cgroup")
+    .and_then(|cgroup_string| parse_cgroup(&cgroup_string))
+    .unwrap_or_default();
 
-  let io_path = format!("/proc/{name}/io");
-  let io_string = read_to_string(io_path).ok()?;
+  let schedstat = parse_proc_file(
+    &name, "schedstat", read("schedstat"), parse_schedstat, parse_errors
+  );
 
-  let status = parse_status(&status_string)?;
-  let stat = parse_stat(&stat_string)?;
-  let io = parse_io(&io_string)?;
+  let oom_score = read("oom_score")
+    .and_then(|content| content.trim().parse().ok());
+  let oom_score_adj = read("oom_score_adj")
+    .and_then(|content| content.trim().parse().ok());
+
+  // Without status nor stat the process has most likely exited
+  if status.is_none() && stat.is_none() {
+    return None;
+  }
+
+  let container = parse_container_id(&cgroup).or_else(|| {
+    get_namespace_container(&name, &cgroup, own_namespaces?)
+  });
+  let unit = parse_systemd_unit(&cgroup);
 
   let proc = Proc {
     pid,
@@ -204,6 +454,15 @@ pub fn get_proc(entry: &DirEntry) -> Option<Proc> {
     status,
     stat,
     io,
+    schedstat,
+    oom_score,
+    oom_score_adj,
+    restricted,
+    resets: Resets::default(),
+    pids: vec![pid],
+    cgroup,
+    container,
+    unit,
     new: false,
     deleted: false
   };
@@ -212,94 +471,265 @@ pub fn get_proc(entry: &DirEntry) -> Option<Proc> {
   Some(proc)
 }
 
-fn parse_uptime(uptime: &str) -> Uptime {
+/// Parses a CPU list like `0-3,8` as used in `Cpus_allowed_list`
+pub fn parse_cpu_list(cpu_list: &str) -> Option<Vec<usize>> {
+  let mut cpus = Vec::new();
+
+  for range in cpu_list.trim().split(',') {
+    match range.split_once('-') {
+      Some((first, last)) => {
+        let first: usize = first.trim().parse().ok()?;
+        let last: usize = last.trim().parse().ok()?;
+        if first > last || last >= MAX_CPUS { return None; }
+        cpus.extend(first..=last);
+      },
+      None => {
+        let cpu: usize = range.trim().parse().ok()?;
+        if cpu >= MAX_CPUS { return None; }
+        cpus.push(cpu);
+      }
+    }
+  }
+
+  Some(cpus)
+}
+
+/// Returns the IDs of the threads of a process
+pub fn get_threads(pid: i32) -> Vec<i32> {
+  let Ok(tasks) = read_dir(format!("/proc/{pid}/task")) else {
+    return Vec::new();
+  };
+
+  tasks.filter_map(|task| task.ok()?.file_name().to_str()?.parse().ok())
+    .collect()
+}
+
+fn parse_uptime(uptime: &str) -> Result<Uptime, ParseError> {
   let mut bits = uptime.split_whitespace();
 
-  let up_str = bits.next().unwrap();
-  let up = f64::from_str(up_str).unwrap();
+  let up_str = bits.next().context("up")?;
+  let up = f64::from_str(up_str).context("up")?;
 
-  let idle_str = bits.next().unwrap();
-  let idle = f64::from_str(idle_str).unwrap();
+  let idle_str = bits.next().context("idle")?;
+  let idle = f64::from_str(idle_str).context("idle")?;
 
-  Uptime { up, idle }
+  Ok(Uptime { up, idle })
 }
 
-pub fn get_uptime() -> Uptime {
-  let uptime_path = Path::new("/proc/uptime");
-  let uptime = read_to_string(uptime_path).unwrap();
-  parse_uptime(&uptime)
+/// Reads the file at `path` and parses it with `parse`
+fn read_and_parse<T>(
+  path: &Path, parse: impl FnOnce(&str) -> Result<T, ParseError>
+) -> Result<T, MonitorError> {
+  let content = read_lossy(path)
+    .map_err(|err| MonitorError::read(path, err))?;
+  parse(&content).map_err(|err| MonitorError::parse(path, err))
 }
 
-fn parse_mem_info(mem_info_str: &str) -> MemInfo {
-  let mut mem_info = MemInfo {
-    mem_total: 0,
-    mem_free: 0,
-    mem_available: 0,
-    swap_total: 0,
-    swap_free: 0
-  };
+pub fn get_uptime() -> Result<Uptime, MonitorError> {
+  read_and_parse(Path::new("/proc/uptime"), parse_uptime)
+}
 
-  let lines = mem_info_str.split('\n');
+fn parse_load_avg(file_content: &str) -> Result<LoadAvg, ParseError> {
+  let mut parts = file_content.split_whitespace();
 
-  for line in lines {
-    let mut parts = line.split_whitespace();
+  let load_1 = parts.next().context("load_1")?.parse().context("load_1")?;
+  let load_5 = parts.next().context("load_5")?.parse().context("load_5")?;
+  let load_15 = parts.next().context("load_15")?.parse().context("load_15")?;
+  let (running, total) = parts.next()
+    .and_then(|tasks| tasks.split_once('/'))
+    .context("tasks")?;
+  let running = running.parse().context("running")?;
+  let total = total.parse().context("total")?;
+  let last_pid = parts.next().context("last_pid")?
+    .parse().context("last_pid")?;
 
-    let key = parts.next();
-    let value = parts.next().map(|value_str| {
-      u64::from_str(value_str)
-    });
-
-    match (key, value) {
-      (Some("MemTotal:"), Some(value)) => mem_info.mem_total = value.unwrap() * 1024,
-      (Some("MemFree:"), Some(value)) => mem_info.mem_free = value.unwrap() * 1024,
-      (Some("MemAvailable:"), Some(value)) => mem_info.mem_available = value.unwrap() * 1024,
-      (Some("SwapTotal:"), Some(value)) => mem_info.swap_total = value.unwrap() * 1024,
-      (Some("SwapFree:"), Some(value)) => mem_info.swap_free = value.unwrap() * 1024,
+  Ok(LoadAvg { load_1, load_5, load_15, running, total, last_pid })
+}
+
+pub fn get_load_avg() -> Result<LoadAvg, MonitorError> {
+  read_and_parse(Path::new("/proc/loadavg"), parse_load_avg)
+}
+
+fn parse_pressure_line(line: &str) -> Result<PressureLine, ParseError> {
+  let mut pressure_line = PressureLine::default();
+
+  for part in line.split_whitespace().skip(1) {
+    match part.split_once('=').context(part)? {
+      ("avg10", value) => pressure_line.avg10 = value.parse().context("avg10")?,
+      ("avg60", value) => pressure_line.avg60 = value.parse().context("avg60")?,
+      ("avg300", value) => {
+        pressure_line.avg300 = value.parse().context("avg300")?;
+      },
+      ("total", value) => pressure_line.total = value.parse().context("total")?,
       _ => {}
     }
   }
 
-  mem_info
+  Ok(pressure_line)
 }
 
-pub fn get_mem_info() -> MemInfo {
-  let uptime_path = Path::new("/proc/meminfo");
-  let mem_info = read_to_string(uptime_path).unwrap();
-  parse_mem_info(&mem_info)
+fn parse_pressure(file_content: &str) -> Result<Pressure, ParseError> {
+  let mut some = None;
+  let mut full = None;
+
+  for line in file_content.lines() {
+    if line.starts_with("some ") {
+      some = Some(parse_pressure_line(line)?);
+    } else if line.starts_with("full ") {
+      full = Some(parse_pressure_line(line)?);
+    }
+  }
+
+  Ok(Pressure { some: some.context("some")?, full })
 }
 
-fn parse_vm_stat(file_content: &str) -> VmStat {
-  let lines = file_content.split('\n');
+/// Returns `None` when the kernel has no PSI support (or it is disabled)
+pub fn get_pressure() -> Result<Option<PressureInfo>, MonitorError> {
+  let read = |resource: &str| {
+    let path = PathBuf::from(format!("/proc/pressure/{resource}"));
+    // Missing, or not supported when PSI is disabled
+    let Ok(content) = read_lossy(&path) else { return Ok(None); };
+    parse_pressure(&content)
+      .map(Some)
+      .map_err(|err| MonitorError::parse(&path, err))
+  };
 
-  let mut vmstat = VmStat::default();
+  let pressure_info = PressureInfo {
+    cpu: read("cpu")?,
+    memory: read("memory")?,
+    io: read("io")?
+  };
+
+  if pressure_info == PressureInfo::default() {
+    Ok(None)
+  } else {
+    Ok(Some(pressure_info))
+  }
+}
 
-  // let first_line = lines.next().unwrap_or("");
-  // if let Some(value_str) = get_value_str("Name:", first_line) {
-  //   name = value_str;
-  // } else {
-  //   return None;
-  // }
+/// Adds the runqueue statistics of every `cpu<N>` line of `/proc/schedstat`,
+/// the 7th to 9th fields being the running time, the waiting time and the
+/// number of timeslices.
+fn parse_system_schedstat(
+  file_content: &str
+) -> Result<SystemSchedStat, ParseError> {
+  let mut system_schedstat = SystemSchedStat::default();
+  let mut found = false;
+
+  let cpu_lines = file_content.lines().filter(|line| {
+    line.starts_with("cpu") && !line.starts_with("cpus")
+  });
+
+  for line in cpu_lines {
+    let mut fields = line.split_whitespace().skip(7);
+    let mut next = |context| {
+      fields.next().and_then(|field| field.parse::<u64>().ok()).context(context)
+    };
+    let mut add = |total: &mut u64, context| -> Result<(), ParseError> {
+      *total = total.checked_add(next(context)?).context(context)?;
+      Ok(())
+    };
+    add(&mut system_schedstat.run_time, "run_time")?;
+    add(&mut system_schedstat.wait_time, "wait_time")?;
+    add(&mut system_schedstat.timeslices, "timeslices")?;
+    found = true;
+  }
+
+  found.then_some(system_schedstat).context("cpu lines")
+}
+
+/// Returns `None` when the kernel is built without schedstats
+pub fn get_system_schedstat() -> Result<Option<SystemSchedStat>, MonitorError> {
+  let schedstat_path = Path::new("/proc/schedstat");
+  if !schedstat_path.exists() { return Ok(None); }
+  read_and_parse(schedstat_path, parse_system_schedstat).map(Some)
+}
+
+fn parse_mem_info(mem_info_str: &str) -> Result<MemInfo, ParseError> {
+  let mut mem_info = MemInfo::default();
+
+  let lines = mem_info_str.split('\n');
 
   for line in lines {
-    if let Some(value) = get_value("pswpin", line) {
-      vmstat.pswpin = value;
-    }
-    else if let Some(value) = get_value("pswpout", line) {
-      vmstat.pswpout = value;
-    }
+    let mut parts = line.split_whitespace();
+
+    let key = parts.next();
+    let field = match key {
+      Some("MemTotal:") => &mut mem_info.mem_total,
+      Some("MemFree:") => &mut mem_info.mem_free,
+      Some("MemAvailable:") => &mut mem_info.mem_available,
+      Some("Buffers:") => &mut mem_info.buffers,
+      Some("Cached:") => &mut mem_info.cached,
+      Some("SwapCached:") => &mut mem_info.swap_cached,
+      Some("Active(anon):") => &mut mem_info.active_anon,
+      Some("Inactive(anon):") => &mut mem_info.inactive_anon,
+      Some("Active(file):") => &mut mem_info.active_file,
+      Some("Inactive(file):") => &mut mem_info.inactive_file,
+      Some("SwapTotal:") => &mut mem_info.swap_total,
+      Some("SwapFree:") => &mut mem_info.swap_free,
+      Some("Zswap:") => &mut mem_info.zswap,
+      Some("Zswapped:") => &mut mem_info.zswapped,
+      Some("Dirty:") => &mut mem_info.dirty,
+      Some("Writeback:") => &mut mem_info.writeback,
+      Some("Shmem:") => &mut mem_info.shmem,
+      Some("Slab:") => &mut mem_info.slab,
+      Some("SReclaimable:") => &mut mem_info.s_reclaimable,
+      Some("KernelStack:") => &mut mem_info.kernel_stack,
+      Some("PageTables:") => &mut mem_info.page_tables,
+      Some("CommitLimit:") => &mut mem_info.commit_limit,
+      Some("Committed_AS:") => &mut mem_info.committed_as,
+      Some("HugePages_Total:") => &mut mem_info.huge_pages_total,
+      Some("HugePages_Free:") => &mut mem_info.huge_pages_free,
+      Some("HugePages_Rsvd:") => &mut mem_info.huge_pages_rsvd,
+      Some("HugePages_Surp:") => &mut mem_info.huge_pages_surp,
+      Some("Hugepagesize:") => &mut mem_info.hugepagesize,
+      _ => continue
+    };
+    let key = key.unwrap_or_default().trim_end_matches(':');
+
+    let value: u64 = parts.next().context(key)?.parse().context(key)?;
+    // Most values are in kB, the HugePages_* counts have no unit
+    let multiplier = if parts.next() == Some("kB") { 1024 } else { 1 };
+
+    *field = value.checked_mul(multiplier).context(key)?;
+  }
+
+  // Every kernel has it, without it this is not a meminfo file
+  if mem_info.mem_total == 0 {
+    return Err(ParseError::new("MemTotal"));
+  }
+
+  Ok(mem_info)
+}
+
+pub fn get_mem_info() -> Result<MemInfo, MonitorError> {
+  read_and_parse(Path::new("/proc/meminfo"), parse_mem_info)
+}
+
+fn parse_vm_stat(file_content: &str) -> Result<VmStat, ParseError> {
+  let values = file_content.lines().map(|line| {
+    let (name, value) = line.split_once(' ').context(line)?;
+    Ok((name.to_string(), value.trim().parse().context(name)?))
+  }).collect::<Result<HashMap<_, _>, _>>()?;
+
+  if values.is_empty() {
+    return Err(ParseError::new("counters"));
   }
 
-  vmstat
+  Ok(VmStat { values })
 }
 
-pub fn get_vm_stat() -> VmStat {
-  let vmstat_path = Path::new("/proc/vmstat");
-  let vmstat = read_to_string(vmstat_path).unwrap();
-  parse_vm_stat(&vmstat)
+pub fn get_vm_stat() -> Result<VmStat, MonitorError> {
+  read_and_parse(Path::new("/proc/vmstat"), parse_vm_stat)
 }
 
-pub fn parse_cpu_info(file_content: &str) -> Option<Vec<CpuInfo>> {
-  file_content.split_terminator("\n\n").map(|cpu_info| {
+/// Parses the `processor` and `cpu MHz` of every CPU in `/proc/cpuinfo`, CPUs
+/// without `cpu MHz` (common on ARM) are left out
+pub fn parse_cpu_info(file_content: &str) -> Result<Vec<CpuInfo>, ParseError> {
+  let mut cpus = Vec::new();
+  let mut found = false;
+
+  for cpu_info in file_content.split_terminator("\n\n") {
     let mut processor = None;
     let mut mhz = None;
 
@@ -307,25 +737,489 @@ pub fn parse_cpu_info(file_content: &str) -> Option<Vec<CpuInfo>> {
       let split = line.split_once(':').map(|v| (v.0.trim(), v.1.trim()));
       if let Some((key, value)) = split {
         match key {
-          "processor" => processor = value.parse().ok(),
-          "cpu MHz"   => mhz = value.parse().ok(),
+          "processor" => {
+            processor = Some(value.parse().context(key)?);
+            found = true;
+          },
+          "cpu MHz"   => mhz = Some(value.parse().context(key)?),
           _ => {}
         }
       }
     }
 
     if let (Some(processor), Some(mhz)) = (processor, mhz) {
+      cpus.push(CpuInfo {
+        processor, mhz, min_mhz: None, max_mhz: None, governor: None
+      });
+    }
+  }
+
+  found.then_some(cpus).context("processor")
+}
+
+
+/// Parses `usage_usec` and `throttled_usec` from a `cpu.stat` file into
+/// `cgroup_stats`
+fn parse_cgroup_cpu_stat(
+  file_content: &str, cgroup_stats: &mut CgroupStats
+) -> Result<(), ParseError> {
+  for line in file_content.lines() {
+    match line.split_once(' ') {
+      Some(("usage_usec", value)) => {
+        cgroup_stats.cpu_usage_usec = value.trim().parse().context("usage_usec")?;
+      },
+      Some(("throttled_usec", value)) => {
+        cgroup_stats.cpu_throttled_usec =
+          value.trim().parse().context("throttled_usec")?;
+      },
+      _ => {}
+    }
+  }
+
+  Ok(())
+}
+
+/// Adds the `rbytes` and `wbytes` of every device in an `io.stat` file into
+/// `cgroup_stats`
+fn parse_cgroup_io_stat(
+  file_content: &str, cgroup_stats: &mut CgroupStats
+) -> Result<(), ParseError> {
+  for line in file_content.lines() {
+    for part in line.split_whitespace().skip(1) {
+      match part.split_once('=') {
+        Some(("rbytes", value)) => {
+          let value = value.parse().context("rbytes")?;
+          cgroup_stats.io_read_bytes = cgroup_stats.io_read_bytes
+            .checked_add(value).context("rbytes")?;
+        },
+        Some(("wbytes", value)) => {
+          let value = value.parse().context("wbytes")?;
+          cgroup_stats.io_write_bytes = cgroup_stats.io_write_bytes
+            .checked_add(value).context("wbytes")?;
+        },
+        _ => {}
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Parses a single value cgroup file like `memory.max`, where `max` means no
+/// limit
+fn parse_cgroup_limit(file_content: &str) -> Result<Option<u64>, ParseError> {
+  match file_content.trim() {
+    "max" => Ok(None),
+    value => value.parse().map(Some).context("value")
+  }
+}
+
+/// Reads the statistics of the cgroup at `/sys/fs/cgroup/<path>`, `path` being
+/// the one from `/proc/<pid>/cgroup`. `None` if the cgroup is gone, files of
+/// controllers not enabled for it are skipped.
+pub fn get_cgroup_stats(path: &str) -> Result<Option<CgroupStats>, MonitorError> {
+  let cgroup_dir = Path::new("/sys/fs/cgroup").join(path.trim_start_matches('/'));
+  if !cgroup_dir.is_dir() { return Ok(None); }
+
+  // Controllers not enabled for the cgroup have no files
+  let read = |file: &str| {
+    let path = cgroup_dir.join(file);
+    read_lossy(&path).ok().map(|content| (path, content))
+  };
+  let parse_limit = |(path, content): (PathBuf, String)| {
+    parse_cgroup_limit(&content).map_err(|err| MonitorError::parse(&path, err))
+  };
+  let mut cgroup_stats = CgroupStats::default();
+
+  if let Some(file) = read("memory.current") {
+    cgroup_stats.memory_current = parse_limit(file)?.unwrap_or(0);
+  }
+  if let Some(file) = read("memory.max") {
+    cgroup_stats.memory_max = parse_limit(file)?;
+  }
+  if let Some(file) = read("memory.swap.current") {
+    cgroup_stats.memory_swap_current = parse_limit(file)?.unwrap_or(0);
+  }
+  if let Some((path, cpu_stat)) = read("cpu.stat") {
+    parse_cgroup_cpu_stat(&cpu_stat, &mut cgroup_stats)
+      .map_err(|err| MonitorError::parse(&path, err))?;
+  }
+  if let Some((path, io_stat)) = read("io.stat") {
+    parse_cgroup_io_stat(&io_stat, &mut cgroup_stats)
+      .map_err(|err| MonitorError::parse(&path, err))?;
+  }
+
+  Ok(Some(cgroup_stats))
+}
+
+/// Returns the entries of `dir` whose name starts with `prefix`, sorted by
+/// name
+fn get_entries(dir: &Path, prefix: &str) -> Vec<PathBuf> {
+  let Ok(entries) = read_dir(dir) else { return Vec::new(); };
+
+  let mut paths: Vec<PathBuf> = entries.filter_map(|entry| {
+    let entry = entry.ok()?;
+    let name = entry.file_name().into_string().ok()?;
+    if name.starts_with(prefix) { Some(entry.path()) } else { None }
+  }).collect();
+  paths.sort();
+
+  paths
+}
+
+/// Reads a sysfs attribute, trimmed
+fn read_attribute(path: &Path) -> Option<String> {
+  read_lossy(path).ok().map(|value| value.trim().to_string())
+}
+
+/// Reads a sysfs attribute in thousandths, like millidegrees Celsius
+fn read_milli_attribute(path: &Path) -> Option<f32> {
+  let value: i64 = read_attribute(path)?.parse().ok()?;
+  Some(value as f32 / 1000f32)
+}
+
+/// Reads `<sysfs_root>/class/thermal/thermal_zone*`, the critical value being
+/// the trip point of type `critical`
+fn get_thermal_zones(sysfs_root: &Path) -> Vec<Sensor> {
+  let thermal_dir = sysfs_root.join("class/thermal");
+
+  get_entries(&thermal_dir, "thermal_zone").iter().filter_map(|zone| {
+    let label = read_attribute(&zone.join("type"))?;
+    let value = read_milli_attribute(&zone.join("temp"))?;
+
+    let critical = get_entries(zone, "trip_point_").iter()
+      .filter_map(|path| path.to_str()?.strip_suffix("_type"))
+      .find(|trip_point| {
+        read_attribute(Path::new(&format!("{trip_point}_type"))).as_deref()
+          == Some("critical")
+      })
+      .and_then(|trip_point| {
+        read_milli_attribute(Path::new(&format!("{trip_point}_temp")))
+      });
+
+    Some(Sensor { label, kind: SensorKind::Temperature, value, critical })
+  }).collect()
+}
+
+/// Reads the `temp*_input` and `fan*_input` files of
+/// `<sysfs_root>/class/hwmon/hwmon*`, labelled with the chip name and the
+/// `*_label` file if present
+fn get_hwmon_sensors(sysfs_root: &Path) -> Vec<Sensor> {
+  let hwmon_dir = sysfs_root.join("class/hwmon");
+  let mut sensors = Vec::new();
+
+  for chip in get_entries(&hwmon_dir, "hwmon") {
+    let chip_name = read_attribute(&chip.join("name")).unwrap_or_default();
+
+    for (prefix, kind) in [("temp", SensorKind::Temperature), ("fan", SensorKind::Fan)] {
+      for input in get_entries(&chip, prefix) {
+        let Some(base) = input.to_str().and_then(|i| i.strip_suffix("_input")) else {
+          continue;
+        };
+        let attribute = |name: &str| PathBuf::from(format!("{base}_{name}"));
+
+        let value = match kind {
+          SensorKind::Temperature => read_milli_attribute(&input),
+          SensorKind::Fan => read_attribute(&input).and_then(|v| v.parse().ok())
+        };
+        let Some(value) = value else { continue; };
+
+        let critical = match kind {
+          SensorKind::Temperature => read_milli_attribute(&attribute("crit")),
+          SensorKind::Fan => None
+        };
+
+        let sensor_name = Path::new(base).file_name()
+          .and_then(|name| name.to_str())
+          .unwrap_or(prefix);
+        let label = read_attribute(&attribute("label"))
+          .unwrap_or_else(|| sensor_name.to_string());
+
+        sensors.push(Sensor {
+          label: format!("{chip_name} {label}").trim().to_string(),
+          kind,
+          value,
+          critical
+        });
+      }
+    }
+  }
+
+  sensors
+}
+
+/// Reads the thermal zones and hwmon sensors under `sysfs_root`, normally
+/// `/sys`
+pub fn get_sensors(sysfs_root: &Path) -> Vec<Sensor> {
+  let mut sensors = get_thermal_zones(sysfs_root);
+  sensors.extend(get_hwmon_sensors(sysfs_root));
+  sensors
+}
+
+/// Reads a cpufreq attribute in kHz as MHz
+fn read_khz_attribute(path: &Path) -> Option<f32> {
+  let khz: f32 = read_attribute(path)?.parse().ok()?;
+  Some(khz / 1000f32)
+}
+
+/// Reads the frequencies from
+/// `<sysfs_root>/devices/system/cpu/cpu<N>/cpufreq`, `None` if there is no
+/// cpufreq driver
+fn get_cpufreq(sysfs_root: &Path) -> Option<Vec<CpuInfo>> {
+  let cpu_dir = sysfs_root.join("devices/system/cpu");
+
+  let mut cpu_info: Vec<CpuInfo> = get_entries(&cpu_dir, "cpu").iter()
+    .filter_map(|cpu| {
+      let name = cpu.file_name()?.to_str()?;
+      let processor = name.strip_prefix("cpu")?.parse().ok()?;
+      let cpufreq = cpu.join("cpufreq");
+
       Some(CpuInfo {
-        processor, mhz
+        processor,
+        mhz: read_khz_attribute(&cpufreq.join("scaling_cur_freq"))?,
+        min_mhz: read_khz_attribute(&cpufreq.join("scaling_min_freq")),
+        max_mhz: read_khz_attribute(&cpufreq.join("scaling_max_freq")),
+        governor: read_attribute(&cpufreq.join("scaling_governor"))
       })
-    } else {
-      None
+    })
+    .collect();
+  cpu_info.sort_by_key(|info| info.processor);
+
+  if cpu_info.is_empty() { None } else { Some(cpu_info) }
+}
+
+/// Reads the CPU frequencies from cpufreq, or from `/proc/cpuinfo` if there is
+/// no cpufreq driver (`cpu MHz` is missing there on many ARM boards)
+pub fn get_cpu_info() -> Result<Vec<CpuInfo>, MonitorError> {
+  match get_cpufreq(Path::new("/sys")) {
+    Some(cpu_info) => Ok(cpu_info),
+    None => read_and_parse(Path::new("/proc/cpuinfo"), parse_cpu_info)
+  }
+}
+
+/// Filesystem types that don't store files on a device
+const PSEUDO_FILESYSTEMS: [&str; 27] = [
+  "autofs", "binfmt_misc", "bpf", "cgroup", "cgroup2", "configfs", "debugfs",
+  "devpts", "devtmpfs", "efivarfs", "fusectl", "hugetlbfs", "mqueue", "nsfs",
+  "proc", "pstore", "ramfs", "rpc_pipefs", "securityfs", "selinuxfs",
+  "squashfs", "sysfs", "tmpfs", "tracefs", "fuse.gvfsd-fuse", "fuse.portal",
+  "overlay"
+];
+
+/// Undoes the octal escapes (like `\040` for spaces) of mountinfo paths
+fn unescape_mount_path(path: &str) -> String {
+  let mut result = String::with_capacity(path.len());
+  let mut rest = path;
+
+  while let Some(index) = rest.find('\\') {
+    result += &rest[..index];
+    let escaped = rest.get(index + 1..index + 4)
+      .and_then(|code| u8::from_str_radix(code, 8).ok());
+    match escaped {
+      Some(byte) => {
+        result.push(byte as char);
+        rest = &rest[index + 4..];
+      },
+      None => {
+        result.push('\\');
+        rest = &rest[index + 1..];
+      }
     }
+  }
+  result += rest;
+
+  result
+}
+
+/// Parses `/proc/<pid>/mountinfo`, see man proc(5)
+fn parse_mount_info(file_content: &str) -> Result<Vec<Mount>, ParseError> {
+  // There is always at least the root of the mount namespace
+  if file_content.trim().is_empty() {
+    return Err(ParseError::new("mounts"));
+  }
+
+  file_content.lines().map(|line| {
+    let (mount_fields, fs_fields) = line.split_once(" - ").context("separator")?;
+    let mut mount_fields = mount_fields.split_whitespace();
+    let mut fs_fields = fs_fields.split_whitespace();
+
+    let device = mount_fields.nth(2).context("major:minor")?.to_string();
+    let mount_point = unescape_mount_path(
+      mount_fields.nth(1).context("mount point")?
+    );
+    let fs_type = fs_fields.next().context("filesystem type")?.to_string();
+    let source = unescape_mount_path(fs_fields.next().context("source")?);
+
+    Ok(Mount { device, mount_point, fs_type, source })
   }).collect()
 }
 
-pub fn get_cpu_info() -> Option<Vec<CpuInfo>> {
-  let cpu_info_path = Path::new("/proc/cpuinfo");
-  let cpu_info = read_to_string(cpu_info_path).unwrap();
-  parse_cpu_info(&cpu_info)
+/// Keeps the mounts of real filesystems, once per device (bind mounts of the
+/// same filesystem are dropped)
+fn filter_mounts(mounts: Vec<Mount>) -> Vec<Mount> {
+  let mut devices = std::collections::HashSet::new();
+
+  mounts.into_iter()
+    .filter(|mount| !PSEUDO_FILESYSTEMS.contains(&mount.fs_type.as_str()))
+    .filter(|mount| devices.insert(mount.device.clone()))
+    .collect()
+}
+
+fn get_filesystem(mount: Mount) -> Option<Filesystem> {
+  let path = std::ffi::CString::new(mount.mount_point.as_str()).ok()?;
+
+  let stat = unsafe {
+    let mut stat: libc::statvfs = std::mem::zeroed();
+    if libc::statvfs(path.as_ptr(), &mut stat) != 0 { return None; }
+    stat
+  };
+
+  let fragment_size = stat.f_frsize as u64;
+  let size = (stat.f_blocks as u64).saturating_mul(fragment_size);
+  if size == 0 { return None; }
+
+  let used = (stat.f_blocks as u64).saturating_sub(stat.f_bfree as u64);
+  Some(Filesystem {
+    mount,
+    size,
+    used: used.saturating_mul(fragment_size),
+    available: (stat.f_bavail as u64).saturating_mul(fragment_size),
+    inodes: stat.f_files as u64,
+    inodes_free: stat.f_ffree as u64
+  })
+}
+
+/// Returns the usage of the mounted filesystems, skipping pseudo filesystems
+pub fn get_filesystems() -> Result<Vec<Filesystem>, MonitorError> {
+  let mounts = read_and_parse(
+    Path::new("/proc/self/mountinfo"), parse_mount_info
+  )?;
+
+  Ok(filter_mounts(mounts).into_iter().filter_map(get_filesystem).collect())
+}
+
+/// Parses `/proc/interrupts` or `/proc/softirqs`: a header with a column per
+/// CPU, then a line per source with its name, a count per CPU (less for
+/// `ERR` and `MIS`) and a description. `description` is used when the line
+/// has none.
+fn parse_interrupts(
+  file_content: &str, description: &str
+) -> Result<Vec<Interrupt>, ParseError> {
+  let mut lines = file_content.lines();
+  let header = lines.next().context("CPU header")?;
+  let cpus = header.split_whitespace()
+    .take_while(|cpu| cpu.starts_with("CPU"))
+    .count();
+  if cpus == 0 {
+    return Err(ParseError::new("CPU header"));
+  }
+
+  lines.map(|line| {
+    let (name, rest) = line.split_once(':').context("name")?;
+    let mut parts = rest.split_whitespace().peekable();
+
+    let mut counts = Vec::with_capacity(cpus);
+    while counts.len() < cpus {
+      let Some(count) = parts.peek().and_then(|part| part.parse().ok()) else {
+        break;
+      };
+      counts.push(count);
+      parts.next();
+    }
+
+    let rest: Vec<&str> = parts.collect();
+    let description = if rest.is_empty() {
+      description.to_string()
+    } else {
+      rest.join(" ")
+    };
+
+    Ok(Interrupt { name: name.trim().to_string(), description, counts })
+  }).collect()
+}
+
+/// Reads the hardware interrupts and the softirqs
+pub fn get_interrupts() -> Result<Interrupts, MonitorError> {
+  let mut list = read_and_parse(
+    Path::new("/proc/interrupts"), |content| parse_interrupts(content, "")
+  )?;
+  list.extend(read_and_parse(
+    Path::new("/proc/softirqs"), |content| parse_interrupts(content, "softirq")
+  )?);
+
+  Ok(Interrupts { list })
+}
+
+/// Parses a zram `mm_stat` file into `zram`, adding to its values. The first
+/// fields are the original size, the compressed size and the memory used.
+fn parse_zram_mm_stat(
+  file_content: &str, zram: &mut CompressedSwap
+) -> Result<(), ParseError> {
+  let mut fields = file_content.split_whitespace();
+  let mut next = |context| {
+    fields.next().and_then(|field| field.parse::<u64>().ok()).context(context)
+  };
+
+  let mut add = |total: &mut u64, context| -> Result<(), ParseError> {
+    *total = total.checked_add(next(context)?).context(context)?;
+    Ok(())
+  };
+  add(&mut zram.original, "orig_data_size")?;
+  add(&mut zram.compressed, "compr_data_size")?;
+  add(&mut zram.memory_used, "mem_used_total")?;
+
+  Ok(())
+}
+
+/// Adds the usage of all the `<sysfs_root>/block/zram*` devices, `None` if
+/// there are none
+pub fn get_zram(sysfs_root: &Path) -> Result<Option<CompressedSwap>, MonitorError> {
+  let mut zram = CompressedSwap::default();
+  let mut found = false;
+
+  for device in get_entries(&sysfs_root.join("block"), "zram") {
+    let path = device.join("mm_stat");
+    // Devices not initialized yet, or kernels before 4.1 without mm_stat
+    let Ok(mm_stat) = read_lossy(&path) else { continue; };
+    parse_zram_mm_stat(&mm_stat, &mut zram)
+      .map_err(|err| MonitorError::parse(&path, err))?;
+    found = true;
+  }
+
+  Ok(found.then_some(zram))
+}
+
+/// Returns the zswap usage, `None` if zswap is disabled and empty. Taken from
+/// `/proc/meminfo` on kernels that have it (5.19+), or from debugfs (only
+/// readable by root).
+pub fn get_zswap(
+  sysfs_root: &Path, mem_info: &MemInfo, page_size: u64
+) -> Option<CompressedSwap> {
+  let enabled = read_attribute(&sysfs_root.join("module/zswap/parameters/enabled"));
+
+  let zswap = if mem_info.zswapped != 0 {
+    CompressedSwap {
+      original: mem_info.zswapped,
+      compressed: mem_info.zswap,
+      memory_used: mem_info.zswap
+    }
+  } else {
+    let debug_dir = sysfs_root.join("kernel/debug/zswap");
+    let read = |name: &str| -> Option<u64> {
+      read_attribute(&debug_dir.join(name))?.parse().ok()
+    };
+    let pool_size = read("pool_total_size").unwrap_or(0);
+
+    CompressedSwap {
+      original: read("stored_pages").unwrap_or(0).saturating_mul(page_size),
+      compressed: pool_size,
+      memory_used: pool_size
+    }
+  };
+
+  if enabled.as_deref() == Some("Y") || zswap.original != 0 {
+    Some(zswap)
+  } else {
+    None
+  }
 }
@@ -2,13 +2,23 @@
 
 use super::*;
 
+/// The error a parser returns when it can't parse `context`
+fn parse_error<T>(context: &str) -> Result<T, ParseError> {
+  Err(ParseError { context: context.to_string() })
+}
+
+/// Path of a file or directory in `examples`
+fn example(name: &str) -> PathBuf {
+  Path::new(env!("CARGO_MANIFEST_DIR")).join("src/parsers/examples").join(name)
+}
+
 static STATUS_EXAMPLE_1: &str = include_str!("./examples/status_example_1.txt");
 static STATUS_EXAMPLE_2: &str = include_str!("./examples/status_example_2.txt");
 static IO_EXAMPLE: &str = include_str!("./examples/io_example.txt");
 
 #[test]
 fn parse_status_1() {
-  let expected = Some(Status {
+  let expected = Ok(Status {
     name: "kworker/0:0-events".to_string(),
     vm_peack: 0,
     vm_size: 0,
@@ -24,7 +34,10 @@ fn parse_status_1() {
     vm_exe: 0,
     vm_lib: 0,
     vm_pte: 0,
-    vm_swap: 0
+    vm_swap: 0,
+    voluntary_ctxt_switches: 127195,
+    nonvoluntary_ctxt_switches: 5,
+    cpus_allowed_list: "0".to_string()
   });
 
   let status = parse_status(STATUS_EXAMPLE_1);
@@ -34,7 +47,7 @@ fn parse_status_1() {
 
 #[test]
 fn parse_status_2() {
-  let expected = Some(Status {
+  let expected = Ok(Status {
     name: "dropbox 2 3 4".to_string(),
     vm_peack: 3393164 * 1024,
     vm_size: 3326428 * 1024,
@@ -50,7 +63,10 @@ fn parse_status_2() {
     vm_exe: 9056 * 1024,
     vm_lib: 91264 * 1024,
     vm_pte: 2112 * 1024,
-    vm_swap: 0 * 1024
+    vm_swap: 0 * 1024,
+    voluntary_ctxt_switches: 105568,
+    nonvoluntary_ctxt_switches: 1919,
+    cpus_allowed_list: "0-7".to_string()
   });
 
   let status = parse_status(STATUS_EXAMPLE_2);
@@ -60,7 +76,7 @@ fn parse_status_2() {
 
 #[test]
 fn parse_status_3() {
-  let expected = None;
+  let expected = parse_error("Name");
 
   let status = parse_status("");
 
@@ -70,9 +86,15 @@ fn parse_status_3() {
 #[test]
 fn parse_stat_1() {
   const STAT_EXAMPLE_1: &str = include_str!("./examples/stat_1.txt");
-  let expected = Some(Stat {
+  let expected = Ok(Stat {
+    minflt: 10,
+    majflt: 12,
     utime: 14,
-    stime: 15
+    stime: 15,
+    nice: 19,
+    starttime: 22,
+    processor: 39,
+    policy: 41
   });
 
   let result = parse_stat(STAT_EXAMPLE_1);
@@ -82,56 +104,190 @@ fn parse_stat_1() {
 
 #[test]
 fn parse_uptime_1() {
-  let expected = Uptime {
+  let expected = Ok(Uptime {
     up: 2978723.18_f64,
     idle: 18677515.22_f64
-  };
+  });
 
   let uptime = parse_uptime("2978723.18 18677515.22");
 
   assert_eq!(expected, uptime);
 }
 
+#[test]
+fn parse_load_avg_1() {
+  const LOAD_AVG_EXAMPLE: &str = include_str!("./examples/loadavg.txt");
+  let expected = Ok(LoadAvg {
+    load_1: 0.27,
+    load_5: 1.12,
+    load_15: 10.04,
+    running: 2,
+    total: 721,
+    last_pid: 23950
+  });
+
+  let load_avg = parse_load_avg(LOAD_AVG_EXAMPLE);
+
+  assert_eq!(expected, load_avg);
+}
+
+#[test]
+fn parse_load_avg_2() {
+  let expected = parse_error("tasks");
+
+  let load_avg = parse_load_avg("0.27 0.12 0.04 2 2395");
+
+  assert_eq!(expected, load_avg);
+}
+
+#[test]
+fn parse_pressure_1() {
+  const PRESSURE_EXAMPLE_1: &str = include_str!("./examples/pressure_1.txt");
+  let expected = Ok(Pressure {
+    some: PressureLine {
+      avg10: 2.85,
+      avg60: 2.30,
+      avg300: 1.13,
+      total: 17769964
+    },
+    full: Some(PressureLine {
+      avg10: 0.05,
+      avg60: 0.41,
+      avg300: 0.28,
+      total: 2067926
+    })
+  });
+
+  let pressure = parse_pressure(PRESSURE_EXAMPLE_1);
+
+  assert_eq!(expected, pressure);
+}
+
+#[test]
+fn parse_pressure_without_full() {
+  let expected = Ok(Pressure {
+    some: PressureLine {
+      avg10: 0.10,
+      avg60: 0.20,
+      avg300: 0.30,
+      total: 1234
+    },
+    full: None
+  });
+
+  let pressure = parse_pressure("some avg10=0.10 avg60=0.20 avg300=0.30 total=1234\n");
+
+  assert_eq!(expected, pressure);
+}
+
+#[test]
+fn parse_pressure_empty() {
+  assert_eq!(parse_error("some"), parse_pressure(""));
+}
+
 static MEM_INFO_EXAMPLE_1: &str = include_str!("./examples/mem_info_example_1.txt");
 
 #[test]
 fn parse_mem_info_1() {
-  let expected = MemInfo {
+  let expected = Ok(MemInfo {
     mem_total: 6 * 1024,
     mem_free: 2* 1024,
     mem_available: 3 * 1024,
     swap_total: 1024 * 1024,
-    swap_free: 512 * 1024
-  };
+    swap_free: 512 * 1024,
+    ..MemInfo::default()
+  });
 
   let uptime = parse_mem_info(MEM_INFO_EXAMPLE_1);
 
   assert_eq!(expected, uptime);
 }
 
+static MEM_INFO_EXAMPLE_2: &str = include_str!("./examples/mem_info_example_2.txt");
+
+#[test]
+fn parse_mem_info_2() {
+  let expected = Ok(MemInfo {
+    mem_total: 6147400 * 1024,
+    mem_free: 4257504 * 1024,
+    mem_available: 5622884 * 1024,
+    buffers: 66736 * 1024,
+    cached: 1484368 * 1024,
+    swap_cached: 128 * 1024,
+    active_anon: 12 * 1024,
+    inactive_anon: 185888 * 1024,
+    active_file: 587216 * 1024,
+    inactive_file: 954840 * 1024,
+    swap_total: 2097148 * 1024,
+    swap_free: 2097020 * 1024,
+    zswap: 384 * 1024,
+    zswapped: 1024 * 1024,
+    dirty: 42884 * 1024,
+    writeback: 16 * 1024,
+    shmem: 9048 * 1024,
+    slab: 90136 * 1024,
+    s_reclaimable: 69236 * 1024,
+    kernel_stack: 1168 * 1024,
+    page_tables: 2156 * 1024,
+    commit_limit: 3073700 * 1024,
+    committed_as: 335944 * 1024,
+    huge_pages_total: 16,
+    huge_pages_free: 12,
+    huge_pages_rsvd: 2,
+    huge_pages_surp: 1,
+    hugepagesize: 2048 * 1024
+  });
+
+  let mem_info = parse_mem_info(MEM_INFO_EXAMPLE_2);
+
+  assert_eq!(expected, mem_info);
+}
 
 static VMSTAT_EXAMPLE: &str = include_str!("./examples/vmstat.txt");
 
 #[test]
 fn test_parse_vm_stat() {
-  let expected = VmStat {
-    pswpin: 174385139,
-    pswpout: 223337038
-  };
-
-  let vmstat = parse_vm_stat(VMSTAT_EXAMPLE);
+  let vmstat = parse_vm_stat(VMSTAT_EXAMPLE).unwrap();
+
+  assert_eq!(9, vmstat.values.len());
+  assert_eq!(174385139, vmstat.get("pswpin"));
+  assert_eq!(223337038, vmstat.get("pswpout"));
+  assert_eq!(1024, vmstat.get("pgpgin"));
+  assert_eq!(7, vmstat.get("oom_kill"));
+  assert_eq!(0, vmstat.get("missing"));
+}
 
-  assert_eq!(expected, vmstat);
+#[test]
+fn vm_stat_gauges_dont_reset() {
+  let last = parse_vm_stat(VMSTAT_EXAMPLE).unwrap();
+
+  let mut vmstat = last.clone();
+  vmstat.values.insert("nr_free_pages".to_string(), 1000);
+  vmstat.values.insert("pgfault".to_string(), 9500);
+  let delta = &vmstat - &last;
+  assert!(!delta.reset);
+  assert_eq!(500, delta.value.get("pgfault"));
+
+  vmstat.values.insert("pgfault".to_string(), 10);
+  assert!((&vmstat - &last).reset);
 }
 
 #[test]
 fn test_parse_cpu_info() {
   const CPU_INFO_EXAMPLE_1: &str = include_str!("./examples/cpu_info_example_1.txt");
-  let expected = Some(vec![
-    CpuInfo { processor: 0, mhz: 2000f32 },
-    CpuInfo { processor: 1, mhz: 2000f32 },
-    CpuInfo { processor: 2, mhz: 2000f32 },
-    CpuInfo { processor: 3, mhz: 2000f32 }
+  let expected = Ok(vec![
+    CpuInfo {
+      processor: 0, mhz: 2000f32, min_mhz: None, max_mhz: None, governor: None
+    },
+    CpuInfo {
+      processor: 1, mhz: 2000f32, min_mhz: None, max_mhz: None, governor: None
+    },
+    CpuInfo {
+      processor: 2, mhz: 2000f32, min_mhz: None, max_mhz: None, governor: None
+    },
+    CpuInfo {
+      processor: 3, mhz: 2000f32, min_mhz: None, max_mhz: None, governor: None
+    }
   ]);
 
 
@@ -142,7 +298,7 @@ fn test_parse_cpu_info() {
 
 #[test]
 fn parse_io_example() {
-  let expected = Some(IoStats {
+  let expected = Ok(IoStats {
     rchar: 1234,
     wchar: 5678,
     syscr: 9,
@@ -156,3 +312,575 @@ fn parse_io_example() {
 
   assert_eq!(expected, io_stats);
 }
+
+#[test]
+fn parse_cgroup_v2() {
+  const CGROUP_EXAMPLE: &str = include_str!("./examples/cgroup_example.txt");
+  let expected = Some("/system.slice/cron.service".to_string());
+
+  let cgroup = parse_cgroup(CGROUP_EXAMPLE);
+
+  assert_eq!(expected, cgroup);
+}
+
+#[test]
+fn parse_cgroup_v1_only() {
+  let cgroup = parse_cgroup("4:memory:/user.slice\n1:cpu:/\n");
+
+  assert_eq!(None, cgroup);
+}
+
+#[test]
+fn parse_cgroup_stat_files() {
+  const CPU_STAT_EXAMPLE: &str = include_str!("./examples/cgroup_cpu_stat.txt");
+  const IO_STAT_EXAMPLE: &str = include_str!("./examples/cgroup_io_stat.txt");
+  let expected = CgroupStats {
+    cpu_usage_usec: 1463040,
+    cpu_throttled_usec: 3200,
+    io_read_bytes: 4096 + 100,
+    io_write_bytes: 8192 + 200,
+    ..CgroupStats::default()
+  };
+
+  let mut cgroup_stats = CgroupStats::default();
+  parse_cgroup_cpu_stat(CPU_STAT_EXAMPLE, &mut cgroup_stats).unwrap();
+  parse_cgroup_io_stat(IO_STAT_EXAMPLE, &mut cgroup_stats).unwrap();
+
+  assert_eq!(expected, cgroup_stats);
+}
+
+#[test]
+fn parse_cgroup_limit_values() {
+  assert_eq!(Ok(Some(536870912)), parse_cgroup_limit("536870912\n"));
+  assert_eq!(Ok(None), parse_cgroup_limit("max\n"));
+}
+
+#[test]
+fn parse_container_ids() {
+  let id = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+  let short_id = Some("0123456789ab".to_string());
+
+  let paths = [
+    format!("/system.slice/docker-{id}.scope"),
+    format!("/docker/{id}"),
+    format!("/system.slice/containerd.service/cri-containerd-{id}.scope"),
+    format!("/machine.slice/libpod-{id}.scope/container"),
+    format!("/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod1234.slice/crio-{id}.scope"),
+    format!("/kubepods/burstable/pod1234/{id}")
+  ];
+
+  for path in paths {
+    assert_eq!(short_id, parse_container_id(&path), "{path}");
+  }
+}
+
+#[test]
+fn parse_container_ids_outside_containers() {
+  let id = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+  assert_eq!(None, parse_container_id("/"));
+  assert_eq!(None, parse_container_id("/system.slice/docker.service"));
+  assert_eq!(None, parse_container_id("/user.slice/user-1000.slice/session-2.scope"));
+  assert_eq!(None, parse_container_id(&format!("/system.slice/{id}.scope")));
+}
+
+#[test]
+fn parse_namespace_inodes() {
+  assert_eq!(Some(4026531836), parse_namespace_inode("pid:[4026531836]"));
+  assert_eq!(None, parse_namespace_inode("pid:4026531836"));
+}
+
+#[test]
+fn namespace_containers() {
+  let own = Namespaces { pid: 1, mnt: 2, uts: 3 };
+  let namespaces = |pid, mnt, uts| Namespaces { pid, mnt, uts };
+
+  // LXC or unknown runtimes
+  assert!(is_namespace_container(&namespaces(4, 5, 6), &own, "/"));
+  assert!(is_namespace_container(
+    &namespaces(4, 2, 3), &own, "/lxc.payload.web/init.scope"
+  ));
+  assert!(is_namespace_container(
+    &namespaces(4, 5, 3), &own, "/machine.slice/machine-debian.scope/payload"
+  ));
+
+  // Sandboxes like Chrome renderers or Flatpak apps
+  assert!(!is_namespace_container(
+    &namespaces(4, 2, 3), &own, "/user.slice/user-1000.slice/session-2.scope"
+  ));
+  assert!(!is_namespace_container(
+    &namespaces(4, 5, 3), &own, "/user.slice/user-1000.slice/app-flatpak.scope"
+  ));
+
+  assert!(!is_namespace_container(&namespaces(1, 5, 6), &own, "/"));
+}
+
+#[test]
+fn parse_systemd_units() {
+  let units = [
+    ("/system.slice/cron.service", Some("cron.service")),
+    ("/system.slice/postgresql.service/worker", Some("postgresql.service")),
+    ("/user.slice/user-1000.slice/session-2.scope", Some("session-2.scope")),
+    ("/user.slice/user-1000.slice/user@1000.service/app.slice/app-firefox.scope",
+      Some("app-firefox.scope")),
+    ("/machine.slice", Some("machine.slice")),
+    ("/", Some("-.slice")),
+    ("/docker/0123456789ab", None),
+    ("", None)
+  ];
+
+  for (cgroup, unit) in units {
+    assert_eq!(unit.map(str::to_string), parse_systemd_unit(cgroup), "{cgroup}");
+  }
+}
+
+#[test]
+fn parse_schedstat_1() {
+  let expected = Ok(SchedStat {
+    run_time: 1538297061,
+    wait_time: 45224,
+    timeslices: 371
+  });
+
+  let schedstat = parse_schedstat("1538297061 45224 371\n");
+
+  assert_eq!(expected, schedstat);
+}
+
+#[test]
+fn parse_system_schedstat_1() {
+  const SCHEDSTAT_EXAMPLE: &str = include_str!("./examples/schedstat.txt");
+  let expected = Ok(SystemSchedStat {
+    run_time: 1000 + 3000,
+    wait_time: 200 + 400,
+    timeslices: 10 + 30
+  });
+
+  let schedstat = parse_system_schedstat(SCHEDSTAT_EXAMPLE);
+
+  assert_eq!(expected, schedstat);
+}
+
+#[test]
+fn parse_cpu_lists() {
+  assert_eq!(Some(vec![0]), parse_cpu_list("0"));
+  assert_eq!(Some(vec![0, 1, 2, 3, 8]), parse_cpu_list("0-3,8\n"));
+  assert_eq!(Some(vec![1, 4, 5]), parse_cpu_list("1, 4-5"));
+  assert_eq!(None, parse_cpu_list(""));
+  assert_eq!(None, parse_cpu_list("3-1"));
+  assert_eq!(None, parse_cpu_list("a"));
+}
+
+#[test]
+fn get_sensors_from_fixture() {
+  let sysfs_root = example("sysfs");
+  let temperature = |label: &str, value, critical| Sensor {
+    label: label.to_string(), kind: SensorKind::Temperature, value, critical
+  };
+  let fan = |label: &str, value| Sensor {
+    label: label.to_string(), kind: SensorKind::Fan, value, critical: None
+  };
+  let expected = vec![
+    temperature("x86_pkg_temp", 45.0, Some(100.0)),
+    temperature("acpitz", 27.8, None),
+    temperature("coretemp Package id 0", 52.0, Some(100.0)),
+    temperature("coretemp temp2", 50.0, Some(100.0)),
+    fan("thinkpad fan1", 2900.0),
+    fan("thinkpad CPU fan", 3100.0)
+  ];
+
+  let sensors = get_sensors(&sysfs_root);
+
+  assert_eq!(expected, sensors);
+}
+
+#[test]
+fn get_sensors_missing_root() {
+  let sensors = get_sensors(Path::new("/nonexistent"));
+
+  assert_eq!(Vec::<Sensor>::new(), sensors);
+}
+
+#[test]
+fn get_cpufreq_from_fixture() {
+  let sysfs_root = example("sysfs");
+  let cpu = |processor, mhz| CpuInfo {
+    processor,
+    mhz,
+    min_mhz: Some(400f32),
+    max_mhz: Some(3600f32),
+    governor: Some("powersave".to_string())
+  };
+  let expected = Some(vec![cpu(0, 1800f32), cpu(1, 3600f32), cpu(10, 900f32)]);
+
+  let cpu_info = get_cpufreq(&sysfs_root);
+
+  assert_eq!(expected, cpu_info);
+}
+
+#[test]
+fn get_cpufreq_missing() {
+  assert_eq!(None, get_cpufreq(Path::new("/nonexistent")));
+}
+
+#[test]
+fn parse_and_filter_mount_info() {
+  const MOUNT_INFO_EXAMPLE: &str = include_str!("./examples/mountinfo.txt");
+  let mount = |device: &str, mount_point: &str, fs_type: &str, source: &str| {
+    Mount {
+      device: device.to_string(),
+      mount_point: mount_point.to_string(),
+      fs_type: fs_type.to_string(),
+      source: source.to_string()
+    }
+  };
+  let expected = vec![
+    mount("259:2", "/", "ext4", "/dev/nvme0n1p2"),
+    mount("259:1", "/boot/efi", "vfat", "/dev/nvme0n1p1"),
+    mount("8:17", "/media/My Disk", "ext4", "/dev/sdb1")
+  ];
+
+  let mounts = parse_mount_info(MOUNT_INFO_EXAMPLE).unwrap();
+  assert_eq!(9, mounts.len());
+
+  let mounts = filter_mounts(mounts);
+  assert_eq!(expected, mounts);
+}
+
+#[test]
+fn unescape_mount_paths() {
+  assert_eq!("/media/My Disk", unescape_mount_path("/media/My\\040Disk"));
+  assert_eq!("/a\\b", unescape_mount_path("/a\\134b"));
+  assert_eq!("/trailing\\", unescape_mount_path("/trailing\\"));
+}
+
+#[test]
+fn parse_interrupts_example() {
+  const INTERRUPTS_EXAMPLE: &str = include_str!("./examples/interrupts.txt");
+  let interrupt = |name: &str, description: &str, counts: Vec<u64>| Interrupt {
+    name: name.to_string(), description: description.to_string(), counts
+  };
+  let expected = vec![
+    interrupt("0", "IO-APIC 2-edge timer", vec![36, 0, 0, 0]),
+    interrupt("8", "IO-APIC 8-edge rtc0", vec![0, 0, 1, 0]),
+    interrupt(
+      "129", "PCI-MSI 1048576-edge eth0-rx-0", vec![120345, 12, 9876543, 0]
+    ),
+    interrupt("NMI", "Non-maskable interrupts", vec![10, 11, 12, 13]),
+    interrupt(
+      "LOC", "Local timer interrupts", vec![5234567, 5123456, 4987654, 4876543]
+    ),
+    interrupt("ERR", "", vec![0]),
+    interrupt("MIS", "", vec![0])
+  ];
+
+  let interrupts = parse_interrupts(INTERRUPTS_EXAMPLE, "");
+
+  assert_eq!(Ok(expected), interrupts);
+}
+
+#[test]
+fn parse_softirqs_example() {
+  const SOFTIRQS_EXAMPLE: &str = include_str!("./examples/softirqs.txt");
+
+  let softirqs = parse_interrupts(SOFTIRQS_EXAMPLE, "softirq").unwrap();
+
+  assert_eq!(3, softirqs.len());
+  assert_eq!("NET_RX", softirqs[2].name);
+  assert_eq!("softirq", softirqs[2].description);
+  assert_eq!(vec![3179, 0, 90000, 0], softirqs[2].counts);
+  assert_eq!(93179, softirqs[2].total());
+}
+
+#[test]
+fn get_zram_from_fixture() {
+  let sysfs_root = example("sysfs");
+  let expected = Some(CompressedSwap {
+    original: 4194304 + 2097152,
+    compressed: 1048576 + 1048576,
+    memory_used: 1310720 + 1048576
+  });
+
+  let zram = get_zram(&sysfs_root).unwrap();
+
+  assert_eq!(expected, zram);
+  assert_eq!(3.0, zram.unwrap().ratio());
+}
+
+#[test]
+fn get_zswap_from_fixture() {
+  let sysfs_root = example("sysfs");
+  let from_debugfs = Some(CompressedSwap {
+    original: 256 * 4096,
+    compressed: 393216,
+    memory_used: 393216
+  });
+  let mem_info = MemInfo {
+    zswap: 1024,
+    zswapped: 4096,
+    ..MemInfo::default()
+  };
+  let from_mem_info = Some(CompressedSwap {
+    original: 4096,
+    compressed: 1024,
+    memory_used: 1024
+  });
+
+  assert_eq!(from_debugfs, get_zswap(&sysfs_root, &MemInfo::default(), 4096));
+  assert_eq!(from_mem_info, get_zswap(&sysfs_root, &mem_info, 4096));
+  assert_eq!(None, get_zswap(Path::new("/nonexistent"), &MemInfo::default(), 4096));
+}
+
+#[test]
+fn read_and_parse_errors() {
+  let missing = read_and_parse(Path::new("/nonexistent/uptime"), parse_uptime);
+  assert!(matches!(missing, Err(MonitorError::Read { .. })));
+
+  let pressure = example("pressure_1.txt");
+  let invalid = read_and_parse(&pressure, parse_uptime).unwrap_err();
+  assert_eq!(
+    format!("Can't parse {}: up", pressure.display()),
+    invalid.to_string()
+  );
+}
+
+#[test]
+fn combined_errors() {
+  let error = |pid| MonitorError::parse(
+    Path::new(&format!("/proc/{pid}/stat")), ParseError::new("utime")
+  );
+
+  assert!(MonitorError::combine(Vec::new()).is_none());
+  assert_eq!(
+    "Can't parse /proc/1/stat: utime",
+    MonitorError::combine(vec![error(1)]).unwrap().to_string()
+  );
+  assert_eq!(
+    "Can't parse /proc/1/stat: utime (and 2 more)",
+    MonitorError::combine(vec![error(1), error(2), error(3)])
+      .unwrap()
+      .to_string()
+  );
+}
+
+#[test]
+fn parse_status_malformed() {
+  assert_eq!(parse_error("Name"), parse_status("Name:"));
+  assert_eq!(parse_error("Name"), parse_status("VmRSS:\t1024 kB\n"));
+  assert_eq!(
+    parse_error("VmRSS"),
+    parse_status("Name:\tcat\nVmRSS:\t18446744073709551615 kB\n")
+  );
+  assert_eq!(
+    parse_error("VmRSS"), parse_status("Name:\tcat\nVmRSS:\tlots kB\n")
+  );
+  assert_eq!(
+    parse_error("voluntary_ctxt_switches"),
+    parse_status("Name:\tcat\nvoluntary_ctxt_switches:\n")
+  );
+}
+
+#[test]
+fn parse_status_non_utf8() {
+  let path = example("status_non_utf8.txt");
+
+  let status = parse_status(&read_lossy(&path).unwrap()).unwrap();
+
+  assert_eq!("bad\u{FFFD}\u{FFFD}name", status.name);
+  assert_eq!(1024 * 1024, status.vm_rss);
+}
+
+#[test]
+fn parse_stat_malformed() {
+  const STAT_PARENTHESES: &str = include_str!("./examples/stat_parentheses.txt");
+
+  assert_eq!(parse_error("comm"), parse_stat(""));
+  assert_eq!(parse_error("comm"), parse_stat("1 (cat S 1 2 3"));
+  assert_eq!(parse_error("minflt"), parse_stat("1 (cat) S 1 2 3 4 5"));
+  assert_eq!(parse_error("minflt"), parse_stat("1 (cat) S 4 5 6 7 8 9 x"));
+
+  // A name with parentheses doesn't shift the fields
+  let stat = parse_stat(STAT_PARENTHESES).unwrap();
+  assert_eq!(10, stat.minflt);
+  assert_eq!(41, stat.policy);
+}
+
+#[test]
+fn parse_io_malformed() {
+  assert_eq!(parse_error("rchar"), parse_io(""));
+  assert_eq!(parse_error("rchar"), parse_io("\0\0\0"));
+  assert_eq!(parse_error("syscr"), parse_io("rchar: 1\nsyscr: -1\n"));
+
+  // A truncated read keeps the fields before the cut
+  let io_stats = parse_io("rchar: 1234\nwchar: 56").unwrap();
+  assert_eq!(1234, io_stats.rchar);
+  assert_eq!(56, io_stats.wchar);
+  assert_eq!(0, io_stats.syscr);
+}
+
+#[test]
+fn parse_schedstat_malformed() {
+  assert_eq!(parse_error("run_time"), parse_schedstat(""));
+  assert_eq!(parse_error("timeslices"), parse_schedstat("1538297061 45224"));
+  assert_eq!(parse_error("wait_time"), parse_schedstat("1 -2 3"));
+}
+
+#[test]
+fn parse_uptime_malformed() {
+  assert_eq!(parse_error("up"), parse_uptime(""));
+  assert_eq!(parse_error("idle"), parse_uptime("2978723.18"));
+  assert_eq!(parse_error("up"), parse_uptime("2978723,18 18677515,22"));
+}
+
+#[test]
+fn parse_load_avg_malformed() {
+  assert_eq!(parse_error("load_1"), parse_load_avg(""));
+  assert_eq!(parse_error("load_15"), parse_load_avg("0.27 1.12"));
+  assert_eq!(parse_error("total"), parse_load_avg("0.27 1.12 10.04 2/ 23950"));
+  assert_eq!(parse_error("last_pid"), parse_load_avg("0.27 1.12 10.04 2/721"));
+}
+
+#[test]
+fn parse_pressure_malformed() {
+  assert_eq!(parse_error("avg10"), parse_pressure("some avg10=x"));
+  assert_eq!(parse_error("avg10"), parse_pressure("some avg10"));
+  assert_eq!(
+    parse_error("total"),
+    parse_pressure("some avg10=0.10 avg60=0.20 avg300=0.30 total=-1")
+  );
+  assert_eq!(parse_error("some"), parse_pressure("full avg10=0.10"));
+}
+
+#[test]
+fn parse_system_schedstat_malformed() {
+  assert_eq!(parse_error("cpu lines"), parse_system_schedstat(""));
+  assert_eq!(
+    parse_error("cpu lines"), parse_system_schedstat("version 15\ntimestamp 1\n")
+  );
+  assert_eq!(
+    parse_error("run_time"), parse_system_schedstat("cpu0 0 0 0 0 0 0\n")
+  );
+  assert_eq!(
+    parse_error("run_time"),
+    parse_system_schedstat(
+      "cpu0 0 0 0 0 0 0 18446744073709551615 0 0\n\
+      cpu1 0 0 0 0 0 0 1 0 0\n"
+    )
+  );
+}
+
+#[test]
+fn parse_mem_info_malformed() {
+  const MEM_INFO_TRUNCATED: &str = include_str!("./examples/mem_info_truncated.txt");
+
+  assert_eq!(parse_error("MemTotal"), parse_mem_info(""));
+  assert_eq!(parse_error("MemTotal"), parse_mem_info("Active: 100 kB\n"));
+  assert_eq!(parse_error("MemAvailable"), parse_mem_info(MEM_INFO_TRUNCATED));
+  assert_eq!(
+    parse_error("MemFree"),
+    parse_mem_info("MemTotal: 100 kB\nMemFree: lots kB\n")
+  );
+  assert_eq!(
+    parse_error("MemTotal"),
+    parse_mem_info("MemTotal: 18446744073709551615 kB\n")
+  );
+}
+
+#[test]
+fn parse_vm_stat_malformed() {
+  assert_eq!(parse_error("counters"), parse_vm_stat("").map(|v| v.values));
+  assert_eq!(parse_error("pswpin"), parse_vm_stat("pswpin").map(|v| v.values));
+  assert_eq!(
+    parse_error("pswpout"),
+    parse_vm_stat("pswpin 1\npswpout x\n").map(|v| v.values)
+  );
+}
+
+#[test]
+fn parse_cpu_info_malformed() {
+  assert_eq!(parse_error("processor"), parse_cpu_info(""));
+  assert_eq!(parse_error("processor"), parse_cpu_info("processor\t: x\n"));
+  assert_eq!(
+    parse_error("cpu MHz"), parse_cpu_info("processor\t: 0\ncpu MHz\t\t: fast\n")
+  );
+
+  // Without cpu MHz, as on many ARM boards
+  assert_eq!(Ok(vec![]), parse_cpu_info("processor\t: 0\nBogoMIPS\t: 48.00\n"));
+}
+
+#[test]
+fn parse_cgroup_files_malformed() {
+  let mut cgroup_stats = CgroupStats::default();
+
+  assert_eq!(
+    parse_error("usage_usec"),
+    parse_cgroup_cpu_stat("usage_usec lots\n", &mut cgroup_stats)
+  );
+  assert_eq!(
+    parse_error("rbytes"),
+    parse_cgroup_io_stat("8:0 rbytes=x wbytes=1\n", &mut cgroup_stats)
+  );
+  assert_eq!(
+    parse_error("wbytes"),
+    parse_cgroup_io_stat(
+      "8:0 wbytes=18446744073709551615\n8:16 wbytes=1\n", &mut cgroup_stats
+    )
+  );
+  assert_eq!(parse_error("value"), parse_cgroup_limit(""));
+  assert_eq!(parse_error("value"), parse_cgroup_limit("-1\n"));
+}
+
+#[test]
+fn parse_mount_info_malformed() {
+  assert_eq!(parse_error("mounts"), parse_mount_info(""));
+  assert_eq!(
+    parse_error("separator"),
+    parse_mount_info("23 28 0:22 / /proc rw,relatime proc proc rw\n")
+  );
+  assert_eq!(
+    parse_error("source"),
+    parse_mount_info("23 28 0:22 / /proc rw,relatime - proc\n")
+  );
+  assert_eq!(
+    parse_error("mount point"), parse_mount_info("23 28 0:22 - proc proc rw\n")
+  );
+}
+
+#[test]
+fn parse_interrupts_malformed() {
+  assert_eq!(parse_error("CPU header"), parse_interrupts("", ""));
+  assert_eq!(
+    parse_error("CPU header"), parse_interrupts("  0:  36  IO-APIC timer\n", "")
+  );
+  assert_eq!(
+    parse_error("name"),
+    parse_interrupts("       CPU0\n  0   36   IO-APIC timer\n", "")
+  );
+
+  // A truncated line keeps the counts it has
+  let interrupts = parse_interrupts("       CPU0       CPU1\n  0:  36", "");
+  assert_eq!(vec![36], interrupts.unwrap()[0].counts);
+}
+
+#[test]
+fn parse_zram_mm_stat_malformed() {
+  let mut zram = CompressedSwap::default();
+
+  assert_eq!(parse_error("orig_data_size"), parse_zram_mm_stat("", &mut zram));
+  assert_eq!(
+    parse_error("mem_used_total"), parse_zram_mm_stat("4096 1024", &mut zram)
+  );
+  assert_eq!(
+    parse_error("orig_data_size"),
+    parse_zram_mm_stat("18446744073709551615 1 1", &mut zram)
+  );
+}
+
+#[test]
+fn parse_cpu_lists_malformed() {
+  assert_eq!(None, parse_cpu_list("0-18446744073709551615"));
+  assert_eq!(None, parse_cpu_list("5000"));
+  assert_eq!(None, parse_cpu_list(&MAX_CPUS.to_string()));
+  assert_eq!(None, parse_cpu_list("0-"));
+  assert_eq!(None, parse_cpu_list(","));
+}
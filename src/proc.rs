@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::ops::{AddAssign, SubAssign, Sub};
 
 /// Stores either a process, or an aggregation of a group of processes
@@ -15,14 +16,53 @@ pub struct Proc {
   /// Number of processes in a group
   pub count: i32,
   pub pid: i32,
-  pub cmdline: String,
-  pub status: Status,
-  pub stat: Stat,
-  pub io: IoStats,
+  /// The files below are `None` when they can't be read, /proc/<pid>/io for
+  /// example needs privileges for other users' processes
+  pub cmdline: Option<String>,
+  pub status: Option<Status>,
+  pub stat: Option<Stat>,
+  pub io: Option<IoStats>,
+  pub schedstat: Option<SchedStat>,
+  /// Badness score the OOM killer uses to pick a victim, the highest in a
+  /// group
+  pub oom_score: Option<i32>,
+  /// Adjustment to the OOM score, the highest in a group
+  pub oom_score_adj: Option<i32>,
+  /// Some files were not readable due to permissions
+  pub restricted: bool,
+  /// Components whose differences are unknown
+  pub resets: Resets,
+  /// PIDs of the processes, more than one in a group
+  pub pids: Vec<i32>,
+  /// cgroup v2 path, relative to the cgroup mount point
+  pub cgroup: String,
+  /// Short ID of the container the process belongs to, if any
+  pub container: Option<String>,
+  /// systemd service, scope or slice the process belongs to
+  pub unit: Option<String>,
   pub new: bool,
   pub deleted: bool
 }
 
+impl Proc {
+
+  /// The name from /proc/<pid>/status, or "-" if it could not be read
+  pub fn name(&self) -> &str {
+    self.status.as_ref().map_or("-", |status| &status.name)
+  }
+
+  /// The container ID, or "host" for processes outside containers
+  pub fn container_name(&self) -> &str {
+    self.container.as_deref().unwrap_or("host")
+  }
+
+  /// The systemd unit, or "-" if the process is not managed by systemd
+  pub fn unit_name(&self) -> &str {
+    self.unit.as_deref().unwrap_or("-")
+  }
+
+}
+
 impl AddAssign for Proc {
 
   fn add_assign(&mut self, rhs: Self) {
@@ -31,12 +71,28 @@ impl AddAssign for Proc {
 
     if !rhs.deleted {
       self.count += rhs.count;
+      self.restricted = self.restricted || rhs.restricted;
+      self.resets.add(rhs.resets);
+
+      match (&mut self.status, rhs.status) {
+        (Some(status), Some(rhs)) => {
+          status.vm_rss += rhs.vm_rss;
+          status.vm_swap += rhs.vm_swap;
+          status.voluntary_ctxt_switches += rhs.voluntary_ctxt_switches;
+          status.nonvoluntary_ctxt_switches += rhs.nonvoluntary_ctxt_switches;
+        },
+        (None, rhs) => self.status = rhs,
+        (Some(_), None) => {}
+      }
 
-      self.status.vm_rss += rhs.status.vm_rss;
-      self.status.vm_swap += rhs.status.vm_swap;
+      add_optional(&mut self.stat, rhs.stat);
+      add_optional(&mut self.io, rhs.io);
+      add_optional(&mut self.schedstat, rhs.schedstat);
 
-      self.stat += rhs.stat;
-      self.io += rhs.io;
+      self.oom_score = self.oom_score.max(rhs.oom_score);
+      self.oom_score_adj = self.oom_score_adj.max(rhs.oom_score_adj);
+
+      self.pids.extend(rhs.pids);
     }
   }
 }
@@ -45,12 +101,188 @@ impl SubAssign for Proc {
 
   /// When subtracting we don't subtrackt most metrics, only CPU etc
   fn sub_assign(&mut self, rhs: Self) {
-    self.stat -= rhs.stat;
-    self.io -= rhs.io;
+    match (&mut self.status, rhs.status) {
+      (Some(status), Some(rhs)) => {
+        let mut subtraction = Subtraction::default();
+        status.voluntary_ctxt_switches = subtraction.sub(
+          status.voluntary_ctxt_switches, rhs.voluntary_ctxt_switches
+        );
+        status.nonvoluntary_ctxt_switches = subtraction.sub(
+          status.nonvoluntary_ctxt_switches, rhs.nonvoluntary_ctxt_switches
+        );
+        self.resets.status = subtraction.reset;
+      },
+      // The rest of the status is still shown, but without the previous
+      // context switches their difference is unknown
+      (Some(status), None) => {
+        status.voluntary_ctxt_switches = 0;
+        status.nonvoluntary_ctxt_switches = 0;
+        self.resets.status = true;
+      },
+      (None, _) => {}
+    }
+    self.resets.stat = sub_optional(&mut self.stat, rhs.stat);
+    self.resets.io = sub_optional(&mut self.io, rhs.io);
+    self.resets.schedstat = sub_optional(&mut self.schedstat, rhs.schedstat);
+  }
+}
+
+/// Components of a `Proc` whose differences are unknown, because one of their
+/// counters went backwards or the process exited. Only set on differences.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct Resets {
+  /// The context switches
+  pub status: bool,
+  pub stat: bool,
+  pub io: bool,
+  pub schedstat: bool
+}
+
+impl Resets {
+
+  pub const ALL: Resets = Resets {
+    status: true, stat: true, io: true, schedstat: true
+  };
+
+  /// Unknown differences make the sum of a group unknown
+  fn add(&mut self, rhs: Resets) {
+    self.status = self.status || rhs.status;
+    self.stat = self.stat || rhs.stat;
+    self.io = self.io || rhs.io;
+    self.schedstat = self.schedstat || rhs.schedstat;
+  }
+
+}
+
+/// Adds a component that may be unavailable, the sum is the known part
+fn add_optional<T: AddAssign>(lhs: &mut Option<T>, rhs: Option<T>) {
+  if let Some(rhs) = rhs {
+    match lhs {
+      Some(lhs) => *lhs += rhs,
+      None => *lhs = Some(rhs)
+    }
+  }
+}
+
+/// Subtracts a component that may be unavailable, the difference is unknown
+/// if the previous value is. Returns whether a counter was reset.
+fn sub_optional<T>(lhs: &mut Option<T>, rhs: Option<T>) -> bool
+where for<'a> &'a T: Sub<&'a T, Output = Delta<T>> {
+  match (lhs.as_ref(), rhs) {
+    (Some(current), Some(last)) => {
+      let delta = current - &last;
+      *lhs = Some(delta.value);
+      delta.reset
+    },
+    _ => {
+      *lhs = None;
+      false
+    }
   }
 }
 
+/// Difference between two readings of some counters
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Delta<T> {
+  pub value: T,
+  /// A counter went backwards, which only happens when it is reset (like a
+  /// device removed and added again). Its difference is unknown, and taken as
+  /// zero.
+  pub reset: bool
+}
+
+/// Subtracts counters, keeping track of the ones that went backwards
+#[derive(Default)]
+struct Subtraction {
+  reset: bool
+}
+
+impl Subtraction {
+
+  fn sub(&mut self, current: u64, last: u64) -> u64 {
+    current.checked_sub(last).unwrap_or_else(|| {
+      self.reset = true;
+      0
+    })
+  }
+
+  fn delta<T>(self, value: T) -> Delta<T> {
+    Delta { value, reset: self.reset }
+  }
+
+}
+
+/// Inodes of the namespaces of a process, from the links in
+/// `/proc/<pid>/ns/`
 #[derive(Clone, Debug, PartialEq)]
+pub struct Namespaces {
+  pub pid: u64,
+  pub mnt: u64,
+  pub uts: u64
+}
+
+/// How processes are aggregated in the list
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GroupBy {
+  None,
+  Name,
+  Cgroup,
+  Container,
+  Unit
+}
+
+impl GroupBy {
+
+  /// The mode `g` switches to
+  pub fn next(self) -> GroupBy {
+    match self {
+      GroupBy::None => GroupBy::Name,
+      GroupBy::Name => GroupBy::Cgroup,
+      GroupBy::Cgroup => GroupBy::Container,
+      GroupBy::Container => GroupBy::Unit,
+      GroupBy::Unit => GroupBy::None
+    }
+  }
+
+  pub fn is_group(self) -> bool {
+    self != GroupBy::None
+  }
+
+  /// Key used to aggregate processes, `None` if not grouping
+  pub fn key(self, proc: &Proc) -> Option<String> {
+    match self {
+      GroupBy::None => None,
+      GroupBy::Name => Some(proc.name().to_string()),
+      GroupBy::Cgroup => Some(proc.cgroup.clone()),
+      GroupBy::Container => Some(proc.container_name().to_string()),
+      GroupBy::Unit => Some(proc.unit_name().to_string())
+    }
+  }
+
+}
+
+/// Which counters of /proc/<pid>/io the read and write columns show
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IoMode {
+  /// Bytes fetched from or sent to the storage layer
+  Storage,
+  /// Bytes passed through read(2) and write(2), page cache hits included
+  Logical
+}
+
+impl IoMode {
+
+  /// The mode `l` switches to
+  pub fn next(self) -> IoMode {
+    match self {
+      IoMode::Storage => IoMode::Logical,
+      IoMode::Logical => IoMode::Storage
+    }
+  }
+
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
 pub struct Status {
   pub name: String,
   pub vm_peack: u64,
@@ -67,7 +299,11 @@ pub struct Status {
   pub vm_exe: u64,
   pub vm_lib: u64,
   pub vm_pte: u64,
-  pub vm_swap: u64
+  pub vm_swap: u64,
+  pub voluntary_ctxt_switches: u64,
+  pub nonvoluntary_ctxt_switches: u64,
+  /// CPUs the process may run on, like `0-3,8`
+  pub cpus_allowed_list: String
 }
 
 #[derive(Clone, Debug, PartialEq, Default)]
@@ -87,29 +323,173 @@ impl Sub<&Uptime> for &Uptime {
   }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+/// System load as reported by `/proc/loadavg`.
+///
+/// See man proc(5) for more details.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct LoadAvg {
+  /// Load average over the last minute
+  pub load_1: f32,
+  /// Load average over the last 5 minutes
+  pub load_5: f32,
+  /// Load average over the last 15 minutes
+  pub load_15: f32,
+  /// Number of currently runnable kernel scheduling entities
+  pub running: u32,
+  /// Number of kernel scheduling entities that currently exist on the system
+  pub total: u32,
+  /// PID of the process that was most recently created on the system
+  pub last_pid: i32
+}
+
+/// One line of a `/proc/pressure/<resource>` file.
+///
+/// The averages are the percentage of time some (or all) tasks were stalled
+/// on the resource, `total` is the absolute stall time in microseconds.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct PressureLine {
+  pub avg10: f32,
+  pub avg60: f32,
+  pub avg300: f32,
+  pub total: u64
+}
+
+/// Pressure Stall Information for a single resource.
+///
+/// `full` is missing for the CPU on kernels older than 5.13.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Pressure {
+  pub some: PressureLine,
+  pub full: Option<PressureLine>
+}
+
+/// Pressure Stall Information, each resource is `None` if the kernel doesn't
+/// provide it.
+///
+/// See https://docs.kernel.org/accounting/psi.html
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct PressureInfo {
+  pub cpu: Option<Pressure>,
+  pub memory: Option<Pressure>,
+  pub io: Option<Pressure>
+}
+
+/// Memory usage from `/proc/meminfo`, in bytes except the `huge_pages_*`
+/// counts.
+///
+/// See man proc(5) for more details.
+#[derive(Clone, Debug, PartialEq, Default)]
 pub struct MemInfo {
   pub mem_total: u64,
   pub mem_free: u64,
   pub mem_available: u64,
+  /// Temporary storage for raw disk blocks
+  pub buffers: u64,
+  /// In-memory cache for files read from the disk (the page cache)
+  pub cached: u64,
+  /// Memory that once was swapped out, is swapped back in but still also is
+  /// in the swap file
+  pub swap_cached: u64,
+  pub active_anon: u64,
+  pub inactive_anon: u64,
+  pub active_file: u64,
+  pub inactive_file: u64,
   pub swap_total: u64,
-  pub swap_free: u64
+  pub swap_free: u64,
+  /// Memory consumed by the zswap backend (compressed size)
+  pub zswap: u64,
+  /// Amount of anonymous memory stored in zswap (original size)
+  pub zswapped: u64,
+  /// Memory which is waiting to get written back to the disk
+  pub dirty: u64,
+  /// Memory which is actively being written back to the disk
+  pub writeback: u64,
+  /// Amount of memory consumed in tmpfs filesystems
+  pub shmem: u64,
+  /// In-kernel data structures cache
+  pub slab: u64,
+  /// Part of Slab, that might be reclaimed, such as caches
+  pub s_reclaimable: u64,
+  pub kernel_stack: u64,
+  /// Amount of memory dedicated to the lowest level of page tables
+  pub page_tables: u64,
+  /// Total amount of memory currently available to be allocated on the
+  /// system, based on the overcommit ratio
+  pub commit_limit: u64,
+  /// The amount of memory presently allocated on the system
+  pub committed_as: u64,
+  /// Size of the pool of huge pages
+  pub huge_pages_total: u64,
+  /// Number of huge pages in the pool that are not yet allocated
+  pub huge_pages_free: u64,
+  /// Number of huge pages reserved but not yet allocated
+  pub huge_pages_rsvd: u64,
+  /// Number of huge pages in the pool above the configured size
+  pub huge_pages_surp: u64,
+  pub hugepagesize: u64
 }
 
+impl MemInfo {
+
+  /// Memory used by processes and the kernel, not counting caches that can be
+  /// reclaimed
+  pub fn used(&self) -> u64 {
+    self.mem_total.saturating_sub(
+      self.mem_free + self.buffers + self.reclaimable_cache()
+    )
+  }
+
+  /// Page cache and reclaimable slab. Shmem (tmpfs, shared anonymous memory)
+  /// is part of `cached` but can only be swapped, so it counts as used.
+  pub fn reclaimable_cache(&self) -> u64 {
+    (self.cached + self.s_reclaimable).saturating_sub(self.shmem)
+  }
+
+}
+
+/// Counters from `/proc/vmstat`, by name.
+///
+/// Most of them are in pages, but `pgpgin` and `pgpgout` are in KiB.
 #[derive(Clone, Debug, PartialEq, Default)]
 pub struct VmStat {
-  pub pswpin: u64,
-  pub pswpout: u64
+  pub values: HashMap<String, u64>
+}
+
+impl VmStat {
+
+  /// Counters shown per interval. The others include gauges like
+  /// `nr_free_pages`, which go down as often as up.
+  pub const COUNTERS: [&'static str; 12] = [
+    "pswpin", "pswpout", "pgpgin", "pgpgout", "pgfault", "pgmajfault",
+    "pgscan_kswapd", "pgscan_direct", "pgsteal_kswapd", "pgsteal_direct",
+    "compact_stall", "oom_kill"
+  ];
+
+  /// Value of a counter, 0 if the kernel doesn't have it
+  pub fn get(&self, name: &str) -> u64 {
+    self.values.get(name).copied().unwrap_or(0)
+  }
+
 }
 
 impl Sub<&VmStat> for &VmStat {
-  type Output = VmStat;
+  type Output = Delta<VmStat>;
 
-  fn sub(self, rhs: &VmStat) -> VmStat {
-    Self::Output {
-      pswpin: self.pswpin - rhs.pswpin,
-      pswpout: self.pswpout - rhs.pswpout
-    }
+  /// Counters missing in `rhs` are kept as they are. Only `COUNTERS` going
+  /// down flag a reset.
+  fn sub(self, rhs: &VmStat) -> Delta<VmStat> {
+    let mut subtraction = Subtraction::default();
+    let values = self.values.iter().map(|(name, &value)| {
+      let last = rhs.get(name);
+      let delta = if VmStat::COUNTERS.contains(&name.as_str()) {
+        subtraction.sub(value, last)
+      } else {
+        value.saturating_sub(last)
+      };
+      (name.clone(), delta)
+    }).collect();
+
+    subtraction.delta(VmStat { values })
   }
 }
 
@@ -118,8 +498,20 @@ impl Sub<&VmStat> for &VmStat {
 /// https://stackoverflow.com/a/60441542/1971526
 ///
 /// man 5 proc
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Default)]
 pub struct Stat {
+  /// (10) minflt  %lu
+  ///
+  /// The number of minor faults the process has made which have not required
+  /// loading a memory page from disk.
+  pub minflt: u64,
+
+  /// (12) majflt  %lu
+  ///
+  /// The number of major faults the process has made which have required
+  /// loading a memory page from disk.
+  pub majflt: u64,
+
   /// (14) utime  %lu
   ///
   /// Amount of time that this process has been scheduled in user mode, measured
@@ -134,29 +526,317 @@ pub struct Stat {
   /// Amount of time that this process has been scheduled in kernel mode,
   /// measured in clock ticks (divide by sysconf(_SC_CLK_TCK)).
   pub stime: u64,
+
+  /// (19) nice  %ld
+  ///
+  /// The nice value (see setpriority(2)), a value in the range 19 (low
+  /// priority) to -20 (high priority).
+  pub nice: i32,
+
+  /// (22) starttime  %llu
+  ///
+  /// The time the process started after system boot, in clock ticks (divide
+  /// by sysconf(_SC_CLK_TCK)).
+  pub starttime: u64,
+
+  /// (39) processor  %d  (since Linux 2.2.8)
+  ///
+  /// CPU number last executed on.
+  pub processor: u32,
+
+  /// (41) policy  %u  (since Linux 2.5.19)
+  ///
+  /// Scheduling policy (see sched_setscheduler(2)).  Decode using the SCHED_*
+  /// constants in linux/sched.h.
+  pub policy: u32,
+}
+
+impl Stat {
+
+  pub fn policy_name(&self) -> &'static str {
+    match self.policy as i32 {
+      libc::SCHED_OTHER => "OTHER",
+      libc::SCHED_FIFO => "FIFO",
+      libc::SCHED_RR => "RR",
+      libc::SCHED_BATCH => "BATCH",
+      libc::SCHED_IDLE => "IDLE",
+      6 => "DL", // SCHED_DEADLINE
+      _ => "?"
+    }
+  }
+
 }
 
 impl AddAssign for Stat {
+
+  /// Only counters are added, `nice`, `starttime`, `processor` and `policy`
+  /// are kept
   fn add_assign(&mut self, rhs: Self) {
+    self.minflt += rhs.minflt;
+    self.majflt += rhs.majflt;
     self.utime += rhs.utime;
     self.stime += rhs.stime;
   }
 }
 
-impl SubAssign for Stat {
+impl Sub<&Stat> for &Stat {
+  type Output = Delta<Stat>;
 
   /// Subtracting `Stat` values is not a typical subtraction. It is meant to
-  /// compute CPU derivatives //TODO:
-  fn sub_assign(&mut self, rhs: Self) {
-    self.utime -= rhs.utime;
-    self.stime -= rhs.stime;
+  /// compute CPU derivatives, the values that are not counters are kept
+  fn sub(self, rhs: &Stat) -> Delta<Stat> {
+    let mut subtraction = Subtraction::default();
+    let stat = Stat {
+      minflt: subtraction.sub(self.minflt, rhs.minflt),
+      majflt: subtraction.sub(self.majflt, rhs.majflt),
+      utime: subtraction.sub(self.utime, rhs.utime),
+      stime: subtraction.sub(self.stime, rhs.stime),
+      ..self.clone()
+    };
+    subtraction.delta(stat)
+  }
+}
+
+/// Scheduler statistics of a process from `/proc/<pid>/schedstat`, all zero
+/// if the kernel was built without `CONFIG_SCHEDSTATS`.
+///
+/// See https://docs.kernel.org/scheduler/sched-stats.html
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct SchedStat {
+  /// Time spent on the CPU, in nanoseconds
+  pub run_time: u64,
+  /// Time spent waiting on a runqueue, in nanoseconds
+  pub wait_time: u64,
+  /// Number of timeslices run on this CPU
+  pub timeslices: u64
+}
+
+impl AddAssign for SchedStat {
+  fn add_assign(&mut self, rhs: Self) {
+    self.run_time += rhs.run_time;
+    self.wait_time += rhs.wait_time;
+    self.timeslices += rhs.timeslices;
+  }
+}
+
+impl Sub<&SchedStat> for &SchedStat {
+  type Output = Delta<SchedStat>;
+
+  fn sub(self, rhs: &SchedStat) -> Delta<SchedStat> {
+    let mut subtraction = Subtraction::default();
+    let schedstat = SchedStat {
+      run_time: subtraction.sub(self.run_time, rhs.run_time),
+      wait_time: subtraction.sub(self.wait_time, rhs.wait_time),
+      timeslices: subtraction.sub(self.timeslices, rhs.timeslices)
+    };
+    subtraction.delta(schedstat)
+  }
+}
+
+/// Runqueue statistics from `/proc/schedstat`, added for all the CPUs
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct SystemSchedStat {
+  /// Time spent running tasks, in nanoseconds
+  pub run_time: u64,
+  /// Time tasks spent waiting on the runqueues, in nanoseconds
+  pub wait_time: u64,
+  /// Number of timeslices run
+  pub timeslices: u64
+}
+
+impl Sub<&SystemSchedStat> for &SystemSchedStat {
+  type Output = Delta<SystemSchedStat>;
+
+  fn sub(self, rhs: &SystemSchedStat) -> Delta<SystemSchedStat> {
+    let mut subtraction = Subtraction::default();
+    let schedstat = SystemSchedStat {
+      run_time: subtraction.sub(self.run_time, rhs.run_time),
+      wait_time: subtraction.sub(self.wait_time, rhs.wait_time),
+      timeslices: subtraction.sub(self.timeslices, rhs.timeslices)
+    };
+    subtraction.delta(schedstat)
+  }
+}
+
+/// Usage of compressed swap in RAM (zram devices or the zswap cache)
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct CompressedSwap {
+  /// Size of the data before compression
+  pub original: u64,
+  /// Size of the data once compressed
+  pub compressed: u64,
+  /// Memory actually consumed, including the allocator overhead
+  pub memory_used: u64
+}
+
+impl CompressedSwap {
+
+  pub fn ratio(&self) -> f32 {
+    if self.compressed == 0 { return 0f32; }
+    self.original as f32 / self.compressed as f32
+  }
+
+}
+
+/// Counters of an interrupt source, from `/proc/interrupts` or
+/// `/proc/softirqs`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Interrupt {
+  /// IRQ number or name, like `129`, `LOC` or `NET_RX`
+  pub name: String,
+  /// Controller, trigger and device of hardware IRQs, or the description of
+  /// the architecture specific ones
+  pub description: String,
+  /// Count per CPU
+  pub counts: Vec<u64>
+}
+
+impl Interrupt {
+
+  pub fn total(&self) -> u64 {
+    self.counts.iter().sum()
+  }
+
+}
+
+/// Interrupt and softirq counters
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Interrupts {
+  pub list: Vec<Interrupt>
+}
+
+impl Sub<&Interrupts> for &Interrupts {
+  type Output = Vec<Delta<Interrupt>>;
+
+  /// Sources missing in `rhs` are kept as they are
+  fn sub(self, rhs: &Interrupts) -> Vec<Delta<Interrupt>> {
+    let last: HashMap<&str, &Interrupt> = rhs.list.iter()
+      .map(|interrupt| (interrupt.name.as_str(), interrupt))
+      .collect();
+
+    self.list.iter().map(|interrupt| {
+      let mut subtraction = Subtraction::default();
+      let mut interrupt = interrupt.clone();
+      if let Some(last) = last.get(interrupt.name.as_str()) {
+        for (count, &last_count) in interrupt.counts.iter_mut().zip(&last.counts) {
+          *count = subtraction.sub(*count, last_count);
+        }
+      }
+      subtraction.delta(interrupt)
+    }).collect()
+  }
+}
+
+/// A mounted filesystem, from `/proc/self/mountinfo`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mount {
+  /// `major:minor` of the device
+  pub device: String,
+  pub mount_point: String,
+  pub fs_type: String,
+  pub source: String
+}
+
+/// Space and inode usage of a mounted filesystem, from statvfs(3)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Filesystem {
+  pub mount: Mount,
+  pub size: u64,
+  pub used: u64,
+  /// Space available to unprivileged users, less than `size - used` because
+  /// of the reserved blocks
+  pub available: u64,
+  pub inodes: u64,
+  pub inodes_free: u64
+}
+
+impl Filesystem {
+
+  /// Percentage of the space used, as `df` calculates it (not counting the
+  /// reserved blocks)
+  pub fn usage_percent(&self) -> f32 {
+    let usable = self.used + self.available;
+    if usable == 0 { return 0f32; }
+    self.used as f32 * 100f32 / usable as f32
+  }
+
+  pub fn inode_usage_percent(&self) -> f32 {
+    if self.inodes == 0 { return 0f32; }
+    self.inodes.saturating_sub(self.inodes_free) as f32 * 100f32 / self.inodes as f32
   }
+
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SensorKind {
+  /// Value in degrees Celsius
+  Temperature,
+  /// Value in RPM
+  Fan
 }
 
+/// A thermal zone or hwmon sensor from sysfs
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sensor {
+  pub label: String,
+  pub kind: SensorKind,
+  pub value: f32,
+  /// Critical value, if the driver reports one
+  pub critical: Option<f32>
+}
+
+/// Frequency of a CPU. The limits and governor are only known when read from
+/// cpufreq.
 #[derive(Clone, Debug, PartialEq)]
 pub struct CpuInfo {
   pub processor: usize,
-  pub mhz: f32
+  pub mhz: f32,
+  pub min_mhz: Option<f32>,
+  pub max_mhz: Option<f32>,
+  pub governor: Option<String>
+}
+
+/// Resource usage of a cgroup v2, read from the files in
+/// `/sys/fs/cgroup/<path>/`.
+///
+/// Files of disabled controllers are read as zero.
+///
+/// See https://docs.kernel.org/admin-guide/cgroup-v2.html
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct CgroupStats {
+  /// memory.current, in bytes
+  pub memory_current: u64,
+  /// memory.max, in bytes, `None` if unlimited
+  pub memory_max: Option<u64>,
+  /// memory.swap.current, in bytes
+  pub memory_swap_current: u64,
+  /// usage_usec from cpu.stat
+  pub cpu_usage_usec: u64,
+  /// throttled_usec from cpu.stat
+  pub cpu_throttled_usec: u64,
+  /// Sum of rbytes for all the devices in io.stat
+  pub io_read_bytes: u64,
+  /// Sum of wbytes for all the devices in io.stat
+  pub io_write_bytes: u64
+}
+
+impl Sub<&CgroupStats> for &CgroupStats {
+  type Output = Delta<CgroupStats>;
+
+  /// Only the counters are subtracted, memory values are kept as they are
+  fn sub(self, rhs: &CgroupStats) -> Delta<CgroupStats> {
+    let mut subtraction = Subtraction::default();
+    let stats = CgroupStats {
+      cpu_usage_usec: subtraction.sub(self.cpu_usage_usec, rhs.cpu_usage_usec),
+      cpu_throttled_usec: subtraction.sub(
+        self.cpu_throttled_usec, rhs.cpu_throttled_usec
+      ),
+      io_read_bytes: subtraction.sub(self.io_read_bytes, rhs.io_read_bytes),
+      io_write_bytes: subtraction.sub(self.io_write_bytes, rhs.io_write_bytes),
+      ..self.clone()
+    };
+    subtraction.delta(stats)
+  }
 }
 
 /// IO statistics for a process and its waited-for children, as reported by
@@ -191,8 +871,23 @@ pub struct IoStats {
 
 impl IoStats {
 
-  pub fn bytes(&self) -> u64 {
-    self.read_bytes + self.read_bytes
+  /// Bytes read, as accounted in `mode`
+  pub fn read(&self, mode: IoMode) -> u64 {
+    match mode {
+      IoMode::Storage => self.read_bytes,
+      IoMode::Logical => self.rchar
+    }
+  }
+
+  /// Bytes written, as accounted in `mode`. Storage writes discount the
+  /// writeback that was cancelled by truncation.
+  pub fn written(&self, mode: IoMode) -> u64 {
+    match mode {
+      IoMode::Storage => {
+        self.write_bytes.saturating_sub(self.cancelled_write_bytes)
+      },
+      IoMode::Logical => self.wchar
+    }
   }
 
 }
@@ -209,14 +904,22 @@ impl AddAssign for IoStats {
   }
 }
 
-impl SubAssign for IoStats {
-  fn sub_assign(&mut self, rhs: Self) {
-    self.rchar -= rhs.rchar;
-    self.wchar -= rhs.wchar;
-    self.syscr -= rhs.syscr;
-    self.syscw -= rhs.syscw;
-    self.read_bytes -= rhs.read_bytes;
-    self.write_bytes -= rhs.write_bytes;
-    self.cancelled_write_bytes -= rhs.cancelled_write_bytes;
+impl Sub<&IoStats> for &IoStats {
+  type Output = Delta<IoStats>;
+
+  fn sub(self, rhs: &IoStats) -> Delta<IoStats> {
+    let mut subtraction = Subtraction::default();
+    let io_stats = IoStats {
+      rchar: subtraction.sub(self.rchar, rhs.rchar),
+      wchar: subtraction.sub(self.wchar, rhs.wchar),
+      syscr: subtraction.sub(self.syscr, rhs.syscr),
+      syscw: subtraction.sub(self.syscw, rhs.syscw),
+      read_bytes: subtraction.sub(self.read_bytes, rhs.read_bytes),
+      write_bytes: subtraction.sub(self.write_bytes, rhs.write_bytes),
+      cancelled_write_bytes: subtraction.sub(
+        self.cancelled_write_bytes, rhs.cancelled_write_bytes
+      )
+    };
+    subtraction.delta(io_stats)
   }
 }
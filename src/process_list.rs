@@ -1,9 +1,22 @@
 use std::collections::HashMap;
 
 use super::proc::Proc;
+use super::proc::Resets;
+use super::proc::CgroupStats;
+use super::proc::Delta;
+use super::proc::GroupBy;
+
+/// Identifies a process across readings. PIDs are reused, so the start time
+/// from /proc/<pid>/stat tells a new process from an old one with the same PID.
+/// It is `None` if stat could not be read.
+type ProcKey = (i32, Option<u64>);
+
+fn proc_key(proc: &Proc) -> ProcKey {
+  (proc.pid, proc.stat.as_ref().map(|stat| stat.starttime))
+}
 
 pub struct ProcessList {
-  lists: [HashMap<i32, Proc>; 2],
+  lists: [HashMap<ProcKey, Proc>; 2],
   last_list: usize
 }
 
@@ -16,26 +29,31 @@ impl ProcessList {
     }
   }
 
+  /// Turns the counters of `list` into differences with the last call. A
+  /// process whose PID was reused is a deleted process plus a new one.
   pub fn on_list(&mut self, list: &mut Vec<Proc>) {
     let current_list = if self.last_list == 1 { 0 } else { 1 };
 
     for process in &mut list.iter_mut() {
-      let pid = process.pid;
+      let key = proc_key(process);
 
-      self.lists[current_list].insert(pid, process.clone());
+      self.lists[current_list].insert(key, process.clone());
 
-      if let Some(last_instance) = self.lists[self.last_list].remove(&pid) {
+      if let Some(last_instance) = self.lists[self.last_list].remove(&key) {
         *process -= last_instance;
       } else {
         process.new = true;
       }
     }
 
-    // Todo: la CPU es incorrecta para los borrados al no haber resta
-    for deleted in &mut self.lists[self.last_list] {
-      deleted.1.deleted = true;
-      let proc = deleted.1;
-      list.push(proc.clone());
+    // Exited processes are shown once more with their last values, but
+    // without counters: what they did since the last reading is unknown
+    for deleted in self.lists[self.last_list].values() {
+      let mut proc = deleted.clone();
+      proc -= deleted.clone();
+      proc.resets = Resets::ALL;
+      proc.deleted = true;
+      list.push(proc);
     }
 
     self.lists[self.last_list].clear();
@@ -43,3 +61,313 @@ impl ProcessList {
     self.last_list = current_list;
   }
 }
+
+/// Keeps the last statistics of every cgroup, so that counters like CPU usage
+/// can be shown per interval
+pub struct CgroupList {
+  last_stats: HashMap<String, CgroupStats>
+}
+
+impl CgroupList {
+
+  pub fn new() -> CgroupList {
+    CgroupList {
+      last_stats: HashMap::new()
+    }
+  }
+
+  /// Returns the statistics of each cgroup relative to the last call, cgroups
+  /// seen for the first time are not included
+  pub fn on_stats(
+    &mut self, stats: HashMap<String, CgroupStats>
+  ) -> HashMap<String, Delta<CgroupStats>> {
+    let deltas = stats.iter().filter_map(|(path, current)| {
+      let last = self.last_stats.get(path)?;
+      Some((path.clone(), current - last))
+    }).collect();
+
+    self.last_stats = stats;
+
+    deltas
+  }
+}
+
+/// Identifies a row of the process list: a process, or a group of them
+#[derive(Clone, Debug, PartialEq)]
+enum RowKey {
+  Process(ProcKey),
+  Group(String)
+}
+
+fn row_key(proc: &Proc, group_by: GroupBy) -> RowKey {
+  match group_by.key(proc) {
+    Some(key) => RowKey::Group(key),
+    None => RowKey::Process(proc_key(proc))
+  }
+}
+
+/// The selected row of the process list. It follows its process or group
+/// when the list is sorted again, and the rows scroll to keep it visible.
+pub struct Selection {
+  /// Rows of the last list, in the order they were shown
+  rows: Vec<RowKey>,
+  /// The selected row, `None` until the first list
+  key: Option<RowKey>,
+  index: usize,
+  /// First row shown
+  first: usize
+}
+
+impl Selection {
+
+  pub fn new() -> Selection {
+    Selection {
+      rows: Vec::new(),
+      key: None,
+      index: 0,
+      first: 0
+    }
+  }
+
+  /// Index of the selected row in `list`
+  pub fn index(&self) -> usize {
+    self.index
+  }
+
+  /// Index of the first row to show
+  pub fn first(&self) -> usize {
+    self.first
+  }
+
+  /// Finds the selected row in `list`, sorted as it will be shown, and
+  /// scrolls so that it is among the `visible` rows. If it is gone, the row
+  /// now at its place is selected.
+  pub fn on_list(&mut self, list: &[Proc], group_by: GroupBy, visible: usize) {
+    self.rows = list.iter().map(|proc| row_key(proc, group_by)).collect();
+
+    let found = self.key.as_ref()
+      .and_then(|key| self.rows.iter().position(|row| row == key));
+    if let Some(index) = found {
+      self.index = index;
+    }
+    self.index = self.index.min(self.rows.len().saturating_sub(1));
+    self.key = self.rows.get(self.index).cloned();
+
+    let visible = visible.max(1);
+    if self.index < self.first {
+      self.first = self.index;
+    } else if self.index >= self.first + visible {
+      self.first = self.index + 1 - visible;
+    }
+    self.first = self.first.min(self.rows.len().saturating_sub(visible));
+  }
+
+  /// Selects the row above, in the last list
+  pub fn up(&mut self) {
+    self.select(self.index.saturating_sub(1));
+  }
+
+  /// Selects the row below, in the last list
+  pub fn down(&mut self) {
+    self.select(self.index + 1);
+  }
+
+  fn select(&mut self, index: usize) {
+    if let Some(key) = self.rows.get(index) {
+      self.index = index;
+      self.key = Some(key.clone());
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::proc::Stat;
+  use crate::proc::Status;
+
+  fn proc(pid: i32, starttime: u64, utime: u64) -> Proc {
+    Proc {
+      count: 1,
+      pid,
+      cmdline: None,
+      status: None,
+      stat: Some(Stat { starttime, utime, ..Default::default() }),
+      io: None,
+      schedstat: None,
+      oom_score: None,
+      oom_score_adj: None,
+      restricted: false,
+      resets: Resets::default(),
+      pids: vec![pid],
+      cgroup: String::new(),
+      container: None,
+      unit: None,
+      new: false,
+      deleted: false
+    }
+  }
+
+  fn utime(proc: &Proc) -> u64 {
+    proc.stat.as_ref().unwrap().utime
+  }
+
+  #[test]
+  fn same_process_is_diffed() {
+    let mut process_list = ProcessList::new();
+    process_list.on_list(&mut vec![proc(10, 500, 100)]);
+
+    let mut list = vec![proc(10, 500, 130)];
+    process_list.on_list(&mut list);
+
+    assert_eq!(1, list.len());
+    assert!(!list[0].new && !list[0].deleted);
+    assert_eq!(30, utime(&list[0]));
+  }
+
+  #[test]
+  fn reused_pid_is_deleted_and_new() {
+    let mut process_list = ProcessList::new();
+    process_list.on_list(&mut vec![proc(10, 500, 100)]);
+
+    // Same PID, started later and with less CPU time than the old one
+    let mut list = vec![proc(10, 900, 5)];
+    process_list.on_list(&mut list);
+
+    assert_eq!(2, list.len());
+    assert!(list[0].new && !list[0].deleted);
+    assert_eq!(5, utime(&list[0]));
+    assert!(list[1].deleted);
+    assert_eq!(Some(500), list[1].stat.as_ref().map(|stat| stat.starttime));
+
+    // The new process is diffed against itself from now on
+    let mut list = vec![proc(10, 900, 25)];
+    process_list.on_list(&mut list);
+
+    assert_eq!(1, list.len());
+    assert!(!list[0].new && !list[0].deleted);
+    assert_eq!(20, utime(&list[0]));
+  }
+
+  #[test]
+  fn counter_reset_is_flagged() {
+    let mut process_list = ProcessList::new();
+    process_list.on_list(&mut vec![proc(10, 500, 100)]);
+
+    let mut list = vec![proc(10, 500, 40)];
+    process_list.on_list(&mut list);
+
+    assert!(list[0].resets.stat);
+    assert!(!list[0].resets.io);
+    assert_eq!(0, utime(&list[0]));
+
+    let mut list = vec![proc(10, 500, 50)];
+    process_list.on_list(&mut list);
+
+    assert!(!list[0].resets.stat);
+    assert_eq!(10, utime(&list[0]));
+  }
+
+  #[test]
+  fn status_without_last_value_is_unknown() {
+    let mut process_list = ProcessList::new();
+    process_list.on_list(&mut vec![proc(10, 500, 100)]);
+
+    let mut current = proc(10, 500, 100);
+    current.status = Some(Status {
+      voluntary_ctxt_switches: 300, vm_rss: 4096, ..Default::default()
+    });
+    let mut list = vec![current];
+    process_list.on_list(&mut list);
+
+    let status = list[0].status.as_ref().unwrap();
+    assert!(list[0].resets.status);
+    assert_eq!(0, status.voluntary_ctxt_switches);
+    assert_eq!(4096, status.vm_rss);
+  }
+
+  #[test]
+  fn cgroup_counter_reset_is_flagged() {
+    let stats = |cpu_usage_usec| HashMap::from([(
+      "system.slice".to_string(),
+      CgroupStats { cpu_usage_usec, memory_current: 4096, ..Default::default() }
+    )]);
+    let mut cgroup_list = CgroupList::new();
+
+    assert!(cgroup_list.on_stats(stats(1000)).is_empty());
+
+    let deltas = cgroup_list.on_stats(stats(1500));
+    assert_eq!(500, deltas["system.slice"].value.cpu_usage_usec);
+    assert!(!deltas["system.slice"].reset);
+
+    // The cgroup was removed and created again
+    let deltas = cgroup_list.on_stats(stats(200));
+    assert_eq!(0, deltas["system.slice"].value.cpu_usage_usec);
+    assert_eq!(4096, deltas["system.slice"].value.memory_current);
+    assert!(deltas["system.slice"].reset);
+  }
+
+  #[test]
+  fn exited_process_is_deleted_once() {
+    let mut process_list = ProcessList::new();
+    process_list.on_list(&mut vec![proc(10, 500, 100), proc(11, 600, 50)]);
+
+    let mut list = vec![proc(11, 600, 60)];
+    process_list.on_list(&mut list);
+
+    assert_eq!(2, list.len());
+    assert!(list[1].deleted);
+    assert_eq!(10, list[1].pid);
+    assert_eq!(0, utime(&list[1]));
+    assert_eq!(Resets::ALL, list[1].resets);
+
+    let mut list = vec![proc(11, 600, 70)];
+    process_list.on_list(&mut list);
+
+    assert_eq!(1, list.len());
+  }
+
+  #[test]
+  fn selection_follows_its_process() {
+    let mut selection = Selection::new();
+    let list = vec![proc(10, 500, 0), proc(11, 600, 0), proc(12, 700, 0)];
+    selection.on_list(&list, GroupBy::None, 10);
+    selection.down();
+
+    // Sorted again, 11 is now the last row
+    let list = vec![proc(12, 700, 0), proc(10, 500, 0), proc(11, 600, 0)];
+    selection.on_list(&list, GroupBy::None, 10);
+    assert_eq!(2, selection.index());
+
+    // Gone, the row at its place is selected
+    let list = vec![proc(12, 700, 0), proc(10, 500, 0)];
+    selection.on_list(&list, GroupBy::None, 10);
+    assert_eq!(1, selection.index());
+    assert_eq!(10, list[selection.index()].pid);
+  }
+
+  #[test]
+  fn selection_is_scrolled_into_view() {
+    let mut selection = Selection::new();
+    let list: Vec<Proc> = (0..10).map(|pid| proc(pid, 0, 0)).collect();
+    selection.on_list(&list, GroupBy::None, 3);
+
+    for _ in 0..4 {
+      selection.down();
+    }
+    selection.on_list(&list, GroupBy::None, 3);
+    assert_eq!(4, selection.index());
+    assert_eq!(2, selection.first());
+
+    selection.up();
+    selection.up();
+    selection.up();
+    selection.on_list(&list, GroupBy::None, 3);
+    assert_eq!(1, selection.index());
+    assert_eq!(1, selection.first());
+
+    // The list got shorter than the rows shown
+    selection.on_list(&list[..2], GroupBy::None, 3);
+    assert_eq!(0, selection.first());
+  }
+}
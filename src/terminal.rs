@@ -9,10 +9,27 @@ use libc::{sysconf, _SC_CLK_TCK}; // TODO: Move out here
 use ncurses::*;
 
 use super::util::humanize;
+use super::util::page_size;
 use super::proc::Proc;
+use super::proc::GroupBy;
+use super::proc::IoMode;
+use super::proc::CgroupStats;
+use super::proc::SystemSchedStat;
+use super::proc::VmStat;
+use super::proc::Sensor;
+use super::proc::SensorKind;
+use super::proc::Filesystem;
+use super::proc::Interrupt;
+use super::proc::Delta;
+use super::proc::CompressedSwap;
 use super::proc::Uptime;
 use super::proc::MemInfo;
+use super::proc::LoadAvg;
+use super::proc::Pressure;
+use super::proc::PressureLine;
+use super::proc::PressureInfo;
 use crate::proc::CpuInfo;
+use crate::error::MonitorError;
 
 pub enum Key {
   Up,
@@ -21,7 +38,19 @@ pub enum Key {
   Right,
   Enter,
   Esc,
-  Group
+  Group,
+  IoMode,
+  Filter,
+  OomScoreAdj,
+  Nice,
+  Policy,
+  Affinity,
+  Kill,
+  Memory,
+  Disks,
+  Interrupts,
+  ScrollLeft,
+  ScrollRight
 }
 
 impl Key {
@@ -34,6 +63,18 @@ impl Key {
       ncurses::KEY_ENTER => Some(Key::Enter),
       27 => Some(Key::Esc),
       103 => Some(Key::Group), // 'g'
+      108 => Some(Key::IoMode), // 'l'
+      102 => Some(Key::Filter), // 'f'
+      111 => Some(Key::OomScoreAdj), // 'o'
+      110 => Some(Key::Nice), // 'n'
+      112 => Some(Key::Policy), // 'p'
+      97 => Some(Key::Affinity), // 'a'
+      107 => Some(Key::Kill), // 'k'
+      109 => Some(Key::Memory), // 'm'
+      100 => Some(Key::Disks), // 'd'
+      105 => Some(Key::Interrupts), // 'i'
+      91 => Some(Key::ScrollLeft), // '['
+      93 => Some(Key::ScrollRight), // ']'
       _ => None
     }
   }
@@ -45,21 +86,123 @@ struct Column<'a> {
   position: i32
 }
 
-static COLUMNS: [Column; 7] = [
-  Column { name: "Name                        ", width: 16, position:  0 },
-  Column { name: "PID                         ", width:  6, position: 17 },
-  Column { name: "[% CPU]                     ", width:  8, position: 24 },
-  Column { name: "RSS                         ", width:  8, position: 32 },
-  Column { name: "Swap                        ", width:  8, position: 41 },
-  Column { name: "Sum                         ", width:  8, position: 50 },
-  Column { name: "IO                          ", width: 10, position: 59 }
+static COLUMNS: [Column; 23] = [
+  Column { name: "Name                        ", width: 16, position:   0 },
+  Column { name: "PID                         ", width:  6, position:  17 },
+  Column { name: "[% CPU]                     ", width:  8, position:  24 },
+  Column { name: "RSS                         ", width:  8, position:  32 },
+  Column { name: "Swap                        ", width:  8, position:  41 },
+  Column { name: "Sum                         ", width:  8, position:  50 },
+  Column { name: "Read/s                      ", width: 10, position:  59 },
+  Column { name: "Write/s                     ", width: 10, position:  70 },
+  Column { name: "SysR/s                      ", width:  8, position:  81 },
+  Column { name: "SysW/s                      ", width:  8, position:  90 },
+  Column { name: "VCSW/s                      ", width:  8, position:  99 },
+  Column { name: "NVCSW/s                     ", width:  8, position: 108 },
+  Column { name: "MinFlt/s                    ", width:  8, position: 117 },
+  Column { name: "MajFlt/s                    ", width:  8, position: 126 },
+  Column { name: "RQ Wait                     ", width:  8, position: 135 },
+  Column { name: "OOM                         ", width:  5, position: 144 },
+  Column { name: "Adj                         ", width:  5, position: 150 },
+  Column { name: "NI                          ", width:  4, position: 156 },
+  Column { name: "Policy                      ", width:  6, position: 161 },
+  Column { name: "CPU                         ", width:  3, position: 168 },
+  Column { name: "Affinity                    ", width: 10, position: 172 },
+  Column { name: "Container                   ", width: 13, position: 183 },
+  Column { name: "Unit                        ", width: 20, position: 197 }
 ];
 
+static FILESYSTEM_COLUMNS: [Column; 8] = [
+  Column { name: "Mount point                 ", width: 20, position:   0 },
+  Column { name: "Type                        ", width:  6, position:  21 },
+  Column { name: "Size                        ", width:  8, position:  28 },
+  Column { name: "Used                        ", width:  8, position:  37 },
+  Column { name: "Available                   ", width:  9, position:  46 },
+  Column { name: "Use                         ", width: 27, position:  56 },
+  Column { name: "Inodes                      ", width:  7, position:  84 },
+  Column { name: "Source                      ", width: 30, position:  92 }
+];
+
+/// Columns of the interrupts screen, followed by a column per CPU
+static INTERRUPT_COLUMNS: [Column; 3] = [
+  Column { name: "IRQ                         ", width:  8, position:   0 },
+  Column { name: "Description                 ", width: 28, position:   9 },
+  Column { name: "Total/s                     ", width:  9, position:  38 }
+];
+
+/// Columns shown after the name and count when grouping by cgroup, in place
+/// of the per-process ones
+static CGROUP_COLUMNS: [Column; 7] = [
+  Column { name: "Memory                      ", width:  8, position:  24 },
+  Column { name: "Limit                       ", width:  8, position:  33 },
+  Column { name: "Swap                        ", width:  8, position:  42 },
+  Column { name: "[% CPU]                     ", width:  8, position:  51 },
+  Column { name: "Throttled                   ", width:  9, position:  60 },
+  Column { name: "Read                        ", width: 10, position:  70 },
+  Column { name: "Write                       ", width: 10, position:  81 }
+];
+
+/// Name and PID stay in place, the columns after them scroll horizontally
+const PINNED_COLUMNS: usize = 2;
+
+/// Width of a row of `columns`, from the start of the first to the end of the
+/// last
+fn row_width(columns: &[Column]) -> usize {
+  columns.last().map_or(0, |column| (column.position + column.width) as usize)
+}
+
+/// Text for a value that could not be read, like /proc/<pid>/io of other
+/// users' processes
+fn or_unavailable(value: Option<String>) -> String {
+  value.unwrap_or_else(|| "-".to_string())
+}
+
+/// Text for a difference of counters, "?" if one of them went backwards in the
+/// interval and the difference is unknown
+fn unless_reset(reset: bool, value: impl FnOnce() -> String) -> String {
+  if reset { "?".to_string() } else { value() }
+}
+
+/// Errors shown at most in the diagnostics panel
+const MAX_DIAGNOSTICS: usize = 4;
+
+/// Color for a load value: yellow when getting close to the number of CPUs,
+/// red when there are more runnable tasks than CPUs.
+fn load_color(load: f32, cpus: f32) -> Option<i16> {
+  if load > cpus {
+    Some(3)
+  } else if load > cpus * 0.7 {
+    Some(4)
+  } else {
+    None
+  }
+}
+
+/// Returns the last `width` characters of `value`, the interesting part of
+/// paths
+fn tail(value: &str, width: usize) -> &str {
+  let count = value.chars().count();
+  if count <= width { return value; }
+
+  let (start, _) = value.char_indices().nth(count - width).unwrap_or((0, ' '));
+  &value[start..]
+}
+
 pub struct Terminal {
   line: i32,
+  delay_ms: i32,
+  /// Result of the last action, shown in the last line
+  status: Option<String>,
+  /// Problems reading the system in this refresh, shown above the status
+  diagnostics: Vec<String>,
   sc_clk_tck: u64,
+  page_size: u64,
   last_update: Instant,
-  elapsed_time: f32
+  elapsed_time: f32,
+  /// Process columns after the pinned ones hidden by scrolling right
+  scroll: usize,
+  /// CPU columns of the interrupts screen hidden by scrolling right
+  cpu_scroll: usize
 }
 
 impl Terminal {
@@ -69,7 +212,8 @@ impl Terminal {
     raw();
     keypad(stdscr(), true);
     noecho();
-    timeout(delay_secs as i32 * 1000);
+    let delay_ms = delay_secs as i32 * 1000;
+    timeout(delay_ms);
     start_color();
 
     init_pair(1, COLOR_BLACK, COLOR_WHITE);
@@ -88,9 +232,15 @@ impl Terminal {
 
     Terminal {
       line: 0,
+      delay_ms,
+      status: None,
+      diagnostics: Vec::new(),
       sc_clk_tck,
+      page_size: page_size(),
       last_update: Instant::now(),
-      elapsed_time: 0f32
+      elapsed_time: 0f32,
+      scroll: 0,
+      cpu_scroll: 0
     }
   }
 
@@ -99,7 +249,9 @@ impl Terminal {
     self.last_update = Instant::now();
   }
 
-  pub fn print_uptime(&mut self, uptime: &Uptime, last_uptime: &Uptime) {
+  pub fn print_uptime(
+    &mut self, uptime: &Uptime, last_uptime: &Uptime, load_avg: Option<&LoadAvg>
+  ) {
     let seconds_up = uptime.up as i32;
     let mut minutes_up = seconds_up / 60;
     let mut hours_up = minutes_up / 60;
@@ -116,19 +268,148 @@ impl Terminal {
     );
     mvaddnstr(self.line, 0, "Uptime: ", 20);
     mvaddnstr(self.line, 8, &formated, 72);
+
+    if let Some(load_avg) = load_avg {
+      let mut position = 8 + formated.len() as i32;
+      mvaddnstr(self.line, position, " | Load:", 8);
+      position += 8;
+
+      for load in [load_avg.load_1, load_avg.load_5, load_avg.load_15] {
+        let formatted = format!(" {load:.2}");
+        let color = load_color(load, cpus as f32);
+        if let Some(color) = color { attron(COLOR_PAIR(color)); }
+        mvaddnstr(self.line, position, &formatted, 10);
+        if let Some(color) = color { attroff(COLOR_PAIR(color)); }
+        position += formatted.len() as i32;
+      }
+
+      let formatted = format!(
+        " | Tasks: {}/{}", load_avg.running, load_avg.total
+      );
+      mvaddnstr(self.line, position, &formatted, 30);
+    }
+
     self.line += 1;
   }
 
-  pub fn print_mem_info(&mut self, mem_info: &MemInfo) {
+  /// Prints the memory summary and a bar splitting the memory between used,
+  /// buffers, page cache and free, plus a detailed breakdown when `expanded`
+  pub fn print_mem_info(&mut self, mem_info: &MemInfo, expanded: bool) {
     let formatted = format!(
       "Memory: [{} / {}] Swap: [{} / {}]",
-      humanize(mem_info.mem_total - mem_info.mem_available),
+      humanize(mem_info.mem_total.saturating_sub(mem_info.mem_available)),
       humanize(mem_info.mem_total),
-      humanize(mem_info.swap_total - mem_info.swap_free),
+      humanize(mem_info.swap_total.saturating_sub(mem_info.swap_free)),
       humanize(mem_info.swap_total)
     );
     mvaddnstr(self.line, 0, &formatted, 80);
     self.line += 1;
+
+    self.print_mem_bar(mem_info);
+
+    if expanded {
+      self.print_mem_breakdown(mem_info);
+    }
+  }
+
+  /// Prints how much the compressed swap in RAM really takes
+  pub fn print_compressed_swap(
+    &mut self, zram: Option<&CompressedSwap>, zswap: Option<&CompressedSwap>
+  ) {
+    let format = |name: &str, swap: &CompressedSwap| format!(
+      "{name}: [{} -> {} ({:.1}x) using {}]",
+      humanize(swap.original),
+      humanize(swap.compressed),
+      swap.ratio(),
+      humanize(swap.memory_used)
+    );
+
+    let parts: Vec<String> = [("zram", zram), ("zswap", zswap)].iter()
+      .filter_map(|(name, swap)| Some(format(name, (*swap)?)))
+      .collect();
+    if parts.is_empty() { return; }
+
+    mvaddnstr(self.line, 0, &parts.join(" "), 120);
+    self.line += 1;
+  }
+
+  fn print_mem_bar(&mut self, mem_info: &MemInfo) {
+    const BAR_WIDTH: u64 = 40;
+
+    if mem_info.mem_total == 0 { return; }
+
+    let cache = mem_info.reclaimable_cache();
+    let segments = [
+      ('U', Some(2), mem_info.used()),
+      ('B', Some(4), mem_info.buffers),
+      ('C', Some(1), cache),
+      (' ', None, mem_info.mem_free)
+    ];
+
+    mvaddnstr(self.line, 0, "[", 1);
+    let mut position = 1;
+    for (symbol, color, value) in segments {
+      let width = (value * BAR_WIDTH / mem_info.mem_total) as i32;
+      let width = width.min(BAR_WIDTH as i32 + 1 - position);
+      if let Some(color) = color { attron(COLOR_PAIR(color)); }
+      let segment = symbol.to_string().repeat(width as usize);
+      mvaddnstr(self.line, position, &segment, width);
+      if let Some(color) = color { attroff(COLOR_PAIR(color)); }
+      position += width;
+    }
+    mvaddnstr(self.line, BAR_WIDTH as i32 + 1, "]", 1);
+
+    let formatted = format!(
+      " Used: {} Buffers: {} Cache: {} Free: {}",
+      humanize(mem_info.used()),
+      humanize(mem_info.buffers),
+      humanize(cache),
+      humanize(mem_info.mem_free)
+    );
+    mvaddnstr(self.line, BAR_WIDTH as i32 + 2, &formatted, 80);
+    self.line += 1;
+  }
+
+  fn print_mem_breakdown(&mut self, mem_info: &MemInfo) {
+    let lines = [
+      format!(
+        "  Cached: {} | Swap cached: {} | Shmem: {} | Dirty: {} | Writeback: {}",
+        humanize(mem_info.cached),
+        humanize(mem_info.swap_cached),
+        humanize(mem_info.shmem),
+        humanize(mem_info.dirty),
+        humanize(mem_info.writeback)
+      ),
+      format!(
+        "  Anon: [Active: {} Inactive: {}] File: [Active: {} Inactive: {}]",
+        humanize(mem_info.active_anon),
+        humanize(mem_info.inactive_anon),
+        humanize(mem_info.active_file),
+        humanize(mem_info.inactive_file)
+      ),
+      format!(
+        "  Slab: {} ({} reclaimable) | Kernel stack: {} | Page tables: {}",
+        humanize(mem_info.slab),
+        humanize(mem_info.s_reclaimable),
+        humanize(mem_info.kernel_stack),
+        humanize(mem_info.page_tables)
+      ),
+      format!(
+        "  Committed: {} / {} | Huge pages: {} free of {} ({} rsvd, {} surp) x {}",
+        humanize(mem_info.committed_as),
+        humanize(mem_info.commit_limit),
+        mem_info.huge_pages_free,
+        mem_info.huge_pages_total,
+        mem_info.huge_pages_rsvd,
+        mem_info.huge_pages_surp,
+        humanize(mem_info.hugepagesize)
+      )
+    ];
+
+    for line in lines {
+      mvaddnstr(self.line, 0, &line, 100);
+      self.line += 1;
+    }
   }
 
   pub fn print_cpu_speed(&mut self, cpu_info: &[CpuInfo]) {
@@ -145,23 +426,256 @@ impl Terminal {
     let min = min_speed;
     let max = max_speed;
     let avg = speed_sum / cpu_info.len() as f32;
-    let formatted = format!("CPU Speed: [Lower: {min:.0} MHz, Avg: {avg:.0} MHz, Max {max:.0} MHz]");
-    mvaddnstr(self.line, 0, &formatted, 80);
+    let mut formatted = format!("CPU Speed: [Lower: {min:.0} MHz, Avg: {avg:.0} MHz, Max {max:.0} MHz]");
+    if let Some(governor) = cpu_info.first().and_then(|info| info.governor.as_ref()) {
+      formatted += &format!(" Governor: {governor}");
+    }
+    mvaddnstr(self.line, 0, &formatted, 100);
     self.line += 1;
+
+    self.print_cpu_cores(cpu_info);
   }
 
-  pub fn print_swap_stats(
-    &mut self, pages_in: u64, pages_out: u64
-  ) {
-    if pages_in != 0 || pages_out != 0 {
-      let pages_in =  humanize(pages_in * 1024 * 4);
-      let pages_out = humanize(pages_out * 1024 * 4);
+  /// Prints the frequency of every core relative to its maximum, only known
+  /// with cpufreq. With too many cores to fit in a few lines, only their
+  /// minimum, average and maximum are shown.
+  fn print_cpu_cores(&mut self, cpu_info: &[CpuInfo]) {
+    const MAX_WIDTH: i32 = 100;
+    const MAX_LINES: i32 = 2;
+
+    let cores: Vec<(usize, f32)> = cpu_info.iter().filter_map(|info| {
+      Some((info.processor, info.mhz * 100f32 / info.max_mhz?))
+    }).collect();
+    if cores.is_empty() { return; }
+
+    let mut placed = Vec::new();
+    let mut line = 0;
+    let mut position = 6;
+    for (processor, percent) in &cores {
+      let formatted = format!(" {processor}:{percent:3.0}%");
+      let width = formatted.len() as i32;
+
+      if position + width > MAX_WIDTH {
+        line += 1;
+        position = 6;
+      }
+
+      placed.push((line, position, formatted));
+      position += width;
+    }
+
+    mvaddnstr(self.line, 0, "Cores:", 6);
+    if line < MAX_LINES {
+      for (line, position, formatted) in placed {
+        mvaddnstr(self.line + line, position, &formatted, MAX_WIDTH);
+      }
+      self.line += line;
+    } else {
+      let percents = cores.iter().map(|(_, percent)| *percent);
+      let min = percents.clone().fold(f32::INFINITY, f32::min);
+      let max = percents.clone().fold(0f32, f32::max);
+      let average = percents.sum::<f32>() / cores.len() as f32;
+      let formatted = format!(
+        " {} cores, min {min:3.0}% avg {average:3.0}% max {max:3.0}%",
+        cores.len()
+      );
+      mvaddnstr(self.line, 6, &formatted, MAX_WIDTH);
+    }
+
+    self.line += 1;
+  }
+
+  /// Prints the sensors in as many lines as needed, temperatures turn yellow
+  /// at 75% and red at 90% of their critical value
+  pub fn print_sensors(&mut self, sensors: &[Sensor]) {
+    const MAX_WIDTH: i32 = 100;
+
+    if sensors.is_empty() { return; }
+
+    mvaddnstr(self.line, 0, "Sensors:", 8);
+    let mut position = 8;
+
+    for sensor in sensors {
+      let value = match sensor.kind {
+        SensorKind::Temperature => format!("{:.0} C", sensor.value),
+        SensorKind::Fan => format!("{:.0} RPM", sensor.value)
+      };
+      let formatted = format!(" [{}: {value}]", sensor.label);
+      let width = formatted.chars().count() as i32;
+
+      if position + width > MAX_WIDTH && position > 8 {
+        self.line += 1;
+        position = 8;
+      }
+
+      let color = sensor.critical.and_then(|critical| {
+        if sensor.value >= critical * 0.9 {
+          Some(3)
+        } else if sensor.value >= critical * 0.75 {
+          Some(4)
+        } else {
+          None
+        }
+      });
+
+      if let Some(color) = color { attron(COLOR_PAIR(color)); }
+      mvaddstr(self.line, position, &formatted);
+      if let Some(color) = color { attroff(COLOR_PAIR(color)); }
+      position += width;
+    }
+
+    self.line += 1;
+  }
+
+  pub fn print_swap_stats(&mut self, delta: &Delta<VmStat>) {
+    let vmstat = &delta.value;
+    let pages_in = vmstat.get("pswpin");
+    let pages_out = vmstat.get("pswpout");
+
+    if delta.reset || pages_in != 0 || pages_out != 0 {
+      let pages_in = unless_reset(delta.reset, || {
+        humanize(pages_in * self.page_size)
+      });
+      let pages_out = unless_reset(delta.reset, || {
+        humanize(pages_out * self.page_size)
+      });
       let formatted = format!("Swap: in: {pages_in} out: {pages_out}");
       mvaddnstr(self.line, 0, &formatted, 80);
       self.line += 1;
     }
   }
 
+  /// Prints the paging and reclaim activity of the last interval, the reclaim
+  /// line is yellow when processes had to reclaim memory themselves and red if
+  /// the OOM killer was invoked
+  pub fn print_paging(&mut self, delta: &Delta<VmStat>) {
+    let vmstat = &delta.value;
+    let kib = |name| unless_reset(delta.reset, || {
+      humanize(vmstat.get(name) * 1024)
+    });
+    let count = |name| unless_reset(delta.reset, || {
+      vmstat.get(name).to_string()
+    });
+
+    let formatted = format!(
+      "Paging: [in: {} out: {}] Faults: [{} major: {}]",
+      kib("pgpgin"),
+      kib("pgpgout"),
+      count("pgfault"),
+      count("pgmajfault")
+    );
+    mvaddnstr(self.line, 0, &formatted, 80);
+    self.line += 1;
+
+    let formatted = format!(
+      "Reclaim: scan [kswapd: {} direct: {}] steal [kswapd: {} direct: {}] \
+      Compaction stalls: {} OOM kills: {}",
+      count("pgscan_kswapd"),
+      count("pgscan_direct"),
+      count("pgsteal_kswapd"),
+      count("pgsteal_direct"),
+      count("compact_stall"),
+      count("oom_kill")
+    );
+    let color = if vmstat.get("oom_kill") != 0 {
+      Some(3)
+    } else if vmstat.get("pgscan_direct") != 0 || vmstat.get("compact_stall") != 0 {
+      Some(4)
+    } else {
+      None
+    };
+
+    if let Some(color) = color { attron(COLOR_PAIR(color)); }
+    mvaddnstr(self.line, 0, &formatted, 120);
+    if let Some(color) = color { attroff(COLOR_PAIR(color)); }
+    self.line += 1;
+  }
+
+  /// Prints one line per resource, highlighted when tasks have been stalled
+  /// for at least a tenth of the last interval (yellow for some, red for full
+  /// stalls)
+  pub fn print_pressure(
+    &mut self, pressure_info: &PressureInfo, last_pressure_info: &PressureInfo
+  ) {
+    let resources = [
+      ("CPU", &pressure_info.cpu, &last_pressure_info.cpu),
+      ("Mem", &pressure_info.memory, &last_pressure_info.memory),
+      ("IO ", &pressure_info.io, &last_pressure_info.io)
+    ];
+
+    for (name, pressure, last_pressure) in resources {
+      if let Some(pressure) = pressure {
+        self.print_pressure_line(name, pressure, last_pressure.as_ref());
+      }
+    }
+  }
+
+  fn print_pressure_line(
+    &mut self, name: &str, pressure: &Pressure, last_pressure: Option<&Pressure>
+  ) {
+    let some = &pressure.some;
+    let mut formatted = format!(
+      "Pressure {name}: some [{:5.2} {:5.2} {:5.2}]",
+      some.avg10, some.avg60, some.avg300
+    );
+    if let Some(full) = &pressure.full {
+      formatted += &format!(
+        " full [{:5.2} {:5.2} {:5.2}]", full.avg10, full.avg60, full.avg300
+      );
+    }
+
+    // Share of the interval tasks spent stalled, `total` is in microseconds
+    const STALLED_SHARE: f32 = 0.1;
+    let stalled = |current: Option<&PressureLine>, last: Option<&PressureLine>| {
+      match (current, last) {
+        (Some(current), Some(last)) => {
+          let stalled_usec = current.total.saturating_sub(last.total);
+          self.per_second(stalled_usec) / 1_000_000f32 >= STALLED_SHARE
+        }
+        _ => false
+      }
+    };
+    let last_some = last_pressure.map(|last| &last.some);
+    let last_full = last_pressure.and_then(|last| last.full.as_ref());
+
+    let color = if stalled(pressure.full.as_ref(), last_full) {
+      Some(3)
+    } else if stalled(Some(some), last_some) {
+      Some(4)
+    } else {
+      None
+    };
+
+    if let Some(color) = color { attron(COLOR_PAIR(color)); }
+    mvaddnstr(self.line, 0, &formatted, 80);
+    if let Some(color) = color { attroff(COLOR_PAIR(color)); }
+    self.line += 1;
+  }
+
+  /// Prints the average time a timeslice waited in the runqueues, and how many
+  /// tasks were waiting on average, for the last interval
+  pub fn print_system_schedstat(&mut self, delta: &Delta<SystemSchedStat>) {
+    let schedstat = &delta.value;
+    let latency_ms = unless_reset(delta.reset, || {
+      let latency_ms = if schedstat.timeslices != 0 {
+        schedstat.wait_time as f32 / schedstat.timeslices as f32 / 1_000_000f32
+      } else {
+        0f32
+      };
+      format!("{latency_ms:.3}")
+    });
+    let waiting = unless_reset(delta.reset, || {
+      let waiting = self.per_second(schedstat.wait_time) / 1_000_000_000f32;
+      format!("{waiting:.2}")
+    });
+
+    let formatted = format!(
+      "Run queue: [Latency: {latency_ms} ms | Waiting tasks: {waiting}]"
+    );
+    mvaddnstr(self.line, 0, &formatted, 80);
+    self.line += 1;
+  }
+
   pub fn print_battery(
     &mut self, percent: i32, rate: f32, hours: i32, minutes: i32
   ) {
@@ -172,28 +686,219 @@ impl Terminal {
     self.line += 1;
   }
 
-  pub fn print_header(&mut self, group: bool, selected_col: usize) {
+  /// Prints the filesystems table, usage turns yellow from `warning` percent
+  /// and red from `critical`
+  pub fn print_filesystems(
+    &mut self, filesystems: &[Filesystem], warning: f32, critical: f32
+  ) {
+    const BAR_WIDTH: usize = 20;
+
+    attron(COLOR_PAIR(1));
+    for column in FILESYSTEM_COLUMNS.iter() {
+      mvaddnstr(self.line, column.position, column.name, column.width + 1);
+    }
+    attroff(COLOR_PAIR(1));
+    self.line += 1;
+
+    let color = |percent: f32| {
+      if percent >= critical {
+        Some(3)
+      } else if percent >= warning {
+        Some(4)
+      } else {
+        None
+      }
+    };
+
+    for filesystem in filesystems {
+      let line = self.line;
+      let mount = &filesystem.mount;
+      let usage = filesystem.usage_percent();
+      let inode_usage = filesystem.inode_usage_percent();
+
+      let filled = (usage / 100f32 * BAR_WIDTH as f32).round() as usize;
+      let filled = filled.min(BAR_WIDTH);
+      let bar = format!(
+        "[{}{}] {usage:3.0}%", "#".repeat(filled), " ".repeat(BAR_WIDTH - filled)
+      );
+
+      let values = [
+        tail(&mount.mount_point, FILESYSTEM_COLUMNS[0].width as usize).to_string(),
+        mount.fs_type.clone(),
+        humanize(filesystem.size),
+        humanize(filesystem.used),
+        humanize(filesystem.available)
+      ];
+      for (column, value) in FILESYSTEM_COLUMNS.iter().zip(values.iter()) {
+        mvaddnstr(line, column.position, value, column.width);
+      }
+
+      let column = &FILESYSTEM_COLUMNS[5];
+      let usage_color = color(usage);
+      if let Some(color) = usage_color { attron(COLOR_PAIR(color)); }
+      mvaddnstr(line, column.position, &bar, column.width);
+      if let Some(color) = usage_color { attroff(COLOR_PAIR(color)); }
+
+      let column = &FILESYSTEM_COLUMNS[6];
+      let inode_color = color(inode_usage);
+      let value = if filesystem.inodes != 0 {
+        format!("{inode_usage:5.1}%")
+      } else {
+        "-".to_string()
+      };
+      if let Some(color) = inode_color { attron(COLOR_PAIR(color)); }
+      mvaddnstr(line, column.position, &value, column.width);
+      if let Some(color) = inode_color { attroff(COLOR_PAIR(color)); }
+
+      let column = &FILESYSTEM_COLUMNS[7];
+      mvaddnstr(line, column.position, &mount.source, column.width);
+
+      self.line += 1;
+    }
+  }
+
+  /// Prints the interrupt sources of the last interval, busiest first, with
+  /// their rate on each CPU. CPUs handling more than half of a busy source are
+  /// highlighted. The CPU columns start at the one `scroll_cpus_right`
+  /// scrolled to.
+  pub fn print_interrupts(&mut self, interrupts: &[Delta<Interrupt>]) {
+    const CPU_WIDTH: i32 = 8;
+    let cpus_position = 48;
+    let cpus = interrupts.iter()
+      .map(|interrupt| interrupt.value.counts.len())
+      .max()
+      .unwrap_or(0);
+    self.cpu_scroll = self.cpu_scroll.min(cpus.saturating_sub(1));
+    let cpu_position = |cpu: usize| {
+      cpus_position + (cpu - self.cpu_scroll) as i32 * CPU_WIDTH
+    };
+
+    attron(COLOR_PAIR(1));
+    mvaddnstr(self.line, 0, &" ".repeat(cpus_position as usize), 8000);
+    for column in INTERRUPT_COLUMNS.iter() {
+      mvaddnstr(self.line, column.position, column.name, column.width + 1);
+    }
+    for cpu in self.cpu_scroll..cpus {
+      let name = format!("{:>7} ", format!("CPU{cpu}"));
+      mvaddnstr(self.line, cpu_position(cpu), &name, CPU_WIDTH);
+    }
+    attroff(COLOR_PAIR(1));
+    self.line += 1;
+
+    let mut list: Vec<_> = interrupts.iter().collect();
+    list.sort_by_key(|interrupt| std::cmp::Reverse(interrupt.value.total()));
+
+    for Delta { value: interrupt, reset } in list {
+      let line = self.line;
+      let total = interrupt.total();
+
+      let values = [
+        interrupt.name.clone(),
+        interrupt.description.clone(),
+        unless_reset(*reset, || {
+          format!("{:8.0}", self.per_second(total))
+        })
+      ];
+      for (column, value) in INTERRUPT_COLUMNS.iter().zip(values.iter()) {
+        mvaddnstr(line, column.position, value, column.width);
+      }
+
+      let counts = interrupt.counts.iter().enumerate().skip(self.cpu_scroll);
+      for (cpu, &count) in counts {
+        let hot = interrupt.counts.len() > 1 && total != 0 && count * 2 > total;
+        let value = unless_reset(*reset, || {
+          format!("{:7.0}", self.per_second(count))
+        });
+
+        if hot { attron(COLOR_PAIR(4)); }
+        mvaddnstr(line, cpu_position(cpu), &value, CPU_WIDTH);
+        if hot { attroff(COLOR_PAIR(4)); }
+      }
+
+      self.line += 1;
+    }
+  }
+
+  pub fn print_filter(&mut self, container: &str) {
+    let formatted = format!("Filter: container {container}");
+    mvaddnstr(self.line, 0, &formatted, 80);
+    self.line += 1;
+  }
+
+  /// Notes that `count` processes are missing values, shown as "-", because
+  /// some of their files in /proc need more privileges
+  pub fn print_restricted(&mut self, count: usize) {
+    let formatted = format!(
+      "Partial data for {count} processes: permission denied, run as root \
+      for all values"
+    );
+    attron(COLOR_PAIR(4));
+    mvaddnstr(self.line, 0, &formatted, 100);
+    attroff(COLOR_PAIR(4));
+    self.line += 1;
+  }
+
+  pub fn print_header(
+    &mut self, group_by: GroupBy, io_mode: IoMode, selected_col: usize
+  ) {
     attron(COLOR_PAIR(1));
 
     for (i, column) in COLUMNS.iter().enumerate() {
+      if group_by == GroupBy::Cgroup && i >= PINNED_COLUMNS { break; }
+      let Some(position) = self.column_position(i) else { continue; };
       let mut column_name = column.name;
 
-      if group && i == 1 {
+      if group_by.is_group() && i == 1 {
         column_name = "Count  ";
       }
+      if group_by == GroupBy::Cgroup && i == 0 {
+        column_name = "Cgroup                      ";
+      }
+      if group_by == GroupBy::Container && i == 0 {
+        column_name = "Container                   ";
+      }
+      if group_by == GroupBy::Unit && i == 0 {
+        column_name = "Unit                        ";
+      }
+      if io_mode == IoMode::Logical && i == 6 {
+        column_name = "RChar/s                     ";
+      }
+      if io_mode == IoMode::Logical && i == 7 {
+        column_name = "WChar/s                     ";
+      }
 
-      mvaddnstr(self.line, column.position, column_name, column.width + 1);
+      mvaddnstr(self.line, position, column_name, column.width + 1);
       if i == selected_col + 1 {
-        mvaddnstr(self.line, column.position -1, ">", 1);
+        mvaddnstr(self.line, position -1, ">", 1);
+      }
+    }
+
+    if group_by == GroupBy::Cgroup {
+      for (i, column) in CGROUP_COLUMNS.iter().enumerate() {
+        mvaddnstr(self.line, column.position, column.name, column.width + 1);
+        // Sort index 0 is the count, the cgroup columns follow it
+        if i + 1 == selected_col {
+          mvaddnstr(self.line, column.position - 1, ">", 1);
+        }
       }
     }
     attroff(COLOR_PAIR(1));
     self.line += 1;
   }
 
-  pub fn print_line(&mut self, proc: &Proc, is_group: bool) {
+  /// Prints a process or group, `cgroup_stats` are the statistics for the
+  /// last interval when grouping by cgroup
+  pub fn print_line(
+    &mut self, proc: &Proc, group_by: GroupBy, io_mode: IoMode,
+    cgroup_stats: Option<&Delta<CgroupStats>>, selected: bool
+  ) {
     let line = self.line;
 
+    if selected {
+      attron(A_REVERSE());
+      mvaddnstr(line, 0, &" ".repeat(self.row_width(group_by)), 8000);
+    }
+
     let color = if proc.new && proc.deleted { Some(4) }
       else if proc.deleted { Some(3) }
       else if proc.new { Some(2) }
@@ -204,13 +909,18 @@ impl Terminal {
       if proc.new { attron(COLOR_PAIR(color)); }
       if proc.deleted { attron(COLOR_PAIR(color)); }
       if proc.new && proc.deleted { attron(COLOR_PAIR(color)); }
-      mvaddnstr(line, 0, &" ".repeat(69), 8000);
+      mvaddnstr(line, 0, &" ".repeat(self.row_width(group_by)), 8000);
     }
 
-    let value = &proc.status.name;
+    let value = match group_by {
+      GroupBy::Cgroup => tail(&proc.cgroup, COLUMNS[0].width as usize),
+      GroupBy::Container => proc.container_name(),
+      GroupBy::Unit => proc.unit_name(),
+      _ => proc.name()
+    };
     mvaddnstr(line, COLUMNS[0].position, value, COLUMNS[0].width);
 
-    if !is_group {
+    if !group_by.is_group() {
       let value = &proc.pid.to_string();
       mvaddnstr(line, COLUMNS[1].position, value, COLUMNS[1].width);
     } else {
@@ -218,46 +928,323 @@ impl Terminal {
       mvaddnstr(line, COLUMNS[1].position, value, COLUMNS[1].width);
     }
 
-    let value = proc.stat.utime + proc.stat.stime;
-    let value = value * 100 / self.sc_clk_tck;
-    let value = if self.elapsed_time != 0f32 {
+    if group_by == GroupBy::Cgroup {
+      if let Some(cgroup_stats) = cgroup_stats {
+        self.print_cgroup_stats(cgroup_stats);
+      }
+    } else {
+      self.print_proc_columns(line, proc, io_mode);
+    }
+
+    if let Some(color) = color {
+      attroff(COLOR_PAIR(color));
+    }
+    if selected {
+      attroff(A_REVERSE());
+    }
+    self.line += 1;
+  }
+
+  /// Prints the per-process columns after the pinned ones
+  fn print_proc_columns(&self, line: i32, proc: &Proc, io_mode: IoMode) {
+    let resets = proc.resets;
+
+    let value = proc.stat.as_ref().map(|stat| unless_reset(resets.stat, || {
+      let value = (stat.utime + stat.stime) * 100 / self.sc_clk_tck;
+      let value = self.per_second(value);
+      format!("{value:7.1} %")
+    }));
+    let value = or_unavailable(value);
+    self.print_column(line, 2, &value);
+
+    let status = proc.status.as_ref();
+
+    let value = or_unavailable(status.map(|status| humanize(status.vm_rss)));
+    self.print_column(line, 3, &value);
+
+    let value = or_unavailable(status.map(|status| humanize(status.vm_swap)));
+    self.print_column(line, 4, &value);
+
+    let value = status.map(|status| humanize(status.vm_rss + status.vm_swap));
+    let value = or_unavailable(value);
+    self.print_column(line, 5, &value);
+
+    let io = proc.io.as_ref();
+
+    let value = io.map(|io| unless_reset(resets.io, || {
+      humanize(self.per_second(io.read(io_mode)) as u64) + "/s"
+    }));
+    let value = or_unavailable(value);
+    self.print_column(line, 6, &value);
+
+    let value = io.map(|io| unless_reset(resets.io, || {
+      humanize(self.per_second(io.written(io_mode)) as u64) + "/s"
+    }));
+    let value = or_unavailable(value);
+    self.print_column(line, 7, &value);
+
+    let value = io.map(|io| unless_reset(resets.io, || {
+      format!("{:7.0}", self.per_second(io.syscr))
+    }));
+    let value = or_unavailable(value);
+    self.print_column(line, 8, &value);
+
+    let value = io.map(|io| unless_reset(resets.io, || {
+      format!("{:7.0}", self.per_second(io.syscw))
+    }));
+    let value = or_unavailable(value);
+    self.print_column(line, 9, &value);
+
+    let value = status.map(|status| unless_reset(resets.status, || {
+      format!("{:7.0}", self.per_second(status.voluntary_ctxt_switches))
+    }));
+    let value = or_unavailable(value);
+    self.print_column(line, 10, &value);
+
+    let value = status.map(|status| unless_reset(resets.status, || {
+      format!("{:7.0}", self.per_second(status.nonvoluntary_ctxt_switches))
+    }));
+    let value = or_unavailable(value);
+    self.print_column(line, 11, &value);
+
+    let stat = proc.stat.as_ref();
+
+    let value = stat.map(|stat| unless_reset(resets.stat, || {
+      format!("{:7.0}", self.per_second(stat.minflt))
+    }));
+    let value = or_unavailable(value);
+    self.print_column(line, 12, &value);
+
+    let value = stat.map(|stat| unless_reset(resets.stat, || {
+      format!("{:7.0}", self.per_second(stat.majflt))
+    }));
+    let value = or_unavailable(value);
+    self.print_column(line, 13, &value);
+
+    // Percentage of the elapsed time spent waiting for a CPU
+    let value = proc.schedstat.as_ref().map(|schedstat| {
+      unless_reset(resets.schedstat, || {
+        let value = self.per_second(schedstat.wait_time) / 10_000_000f32;
+        format!("{value:5.1} %")
+      })
+    });
+    let value = or_unavailable(value);
+    self.print_column(line, 14, &value);
+
+    let value = proc.oom_score.map(|oom_score| format!("{oom_score:4}"));
+    let value = or_unavailable(value);
+    self.print_column(line, 15, &value);
+
+    let value = proc.oom_score_adj.map(|adj| format!("{adj:5}"));
+    let value = or_unavailable(value);
+    self.print_column(line, 16, &value);
+
+    let value = or_unavailable(stat.map(|stat| format!("{:3}", stat.nice)));
+    self.print_column(line, 17, &value);
+
+    let value = stat.map_or("-", |stat| stat.policy_name());
+    self.print_column(line, 18, value);
+
+    let value = stat.map(|stat| format!("{:3}", stat.processor));
+    let value = or_unavailable(value);
+    self.print_column(line, 19, &value);
+
+    let value = status.map_or("-", |status| &status.cpus_allowed_list);
+    self.print_column(line, 20, value);
+
+    let value = proc.container_name();
+    self.print_column(line, 21, value);
+
+    let value = proc.unit_name();
+    self.print_column(line, 22, value);
+  }
+
+  /// Prints `value` in the `i`th of `COLUMNS`, unless scrolled out of view
+  fn print_column(&self, line: i32, i: usize, value: &str) {
+    if let Some(position) = self.column_position(i) {
+      mvaddnstr(line, position, value, COLUMNS[i].width);
+    }
+  }
+
+  /// Position of the `i`th of `COLUMNS` after scrolling, `None` if it is
+  /// scrolled out of view
+  fn column_position(&self, i: usize) -> Option<i32> {
+    if i < PINNED_COLUMNS {
+      return Some(COLUMNS[i].position);
+    }
+
+    let first = PINNED_COLUMNS + self.scroll;
+    if i < first {
+      return None;
+    }
+    let shift = COLUMNS[first].position - COLUMNS[PINNED_COLUMNS].position;
+    Some(COLUMNS[i].position - shift)
+  }
+
+  /// Width of the rows of the process list, to fill the highlighted ones
+  fn row_width(&self, group_by: GroupBy) -> usize {
+    if group_by == GroupBy::Cgroup {
+      return row_width(&CGROUP_COLUMNS);
+    }
+    let shift = COLUMNS[PINNED_COLUMNS + self.scroll].position
+      - COLUMNS[PINNED_COLUMNS].position;
+    row_width(&COLUMNS) - shift as usize
+  }
+
+  /// Shows the process columns further left, undoing `scroll_right`
+  pub fn scroll_left(&mut self) {
+    self.scroll = self.scroll.saturating_sub(1);
+  }
+
+  /// Hides the leftmost process column after the pinned ones, to show the
+  /// ones beyond the right edge of the screen
+  pub fn scroll_right(&mut self) {
+    self.scroll = (self.scroll + 1).min(COLUMNS.len() - PINNED_COLUMNS - 1);
+  }
+
+  /// Shows the CPU columns of the interrupts screen further left, undoing
+  /// `scroll_cpus_right`
+  pub fn scroll_cpus_left(&mut self) {
+    self.cpu_scroll = self.cpu_scroll.saturating_sub(1);
+  }
+
+  /// Hides the leftmost CPU column of the interrupts screen, it is limited to
+  /// the number of CPUs when printing
+  pub fn scroll_cpus_right(&mut self) {
+    self.cpu_scroll += 1;
+  }
+
+  /// Rate of a counter over the last interval
+  fn per_second(&self, value: u64) -> f32 {
+    if self.elapsed_time != 0f32 {
       value as f32 / self.elapsed_time
     } else {
       0f32
+    }
+  }
+
+  fn print_cgroup_stats(&mut self, delta: &Delta<CgroupStats>) {
+    let cgroup_stats = &delta.value;
+    let line = self.line;
+    let elapsed_usec = self.elapsed_time * 1_000_000f32;
+    let rate = |value: u64| {
+      if self.elapsed_time != 0f32 {
+        humanize((value as f32 / self.elapsed_time) as u64) + "/s"
+      } else {
+        "0 B/s".to_string()
+      }
+    };
+    let percent = |usec: u64| {
+      if elapsed_usec != 0f32 {
+        usec as f32 * 100f32 / elapsed_usec
+      } else {
+        0f32
+      }
     };
-    let value = format!("{value:7.1} %");
-    mvaddnstr(line, COLUMNS[2].position, &value, COLUMNS[2].width);
 
-    let value = &humanize(proc.status.vm_rss);
-    mvaddnstr(line, COLUMNS[3].position, value, COLUMNS[3].width);
+    let values = [
+      humanize(cgroup_stats.memory_current),
+      cgroup_stats.memory_max.map_or("max".to_string(), humanize),
+      humanize(cgroup_stats.memory_swap_current),
+      unless_reset(delta.reset, || {
+        format!("{:7.1} %", percent(cgroup_stats.cpu_usage_usec))
+      }),
+      unless_reset(delta.reset, || {
+        format!("{:6.1} %", percent(cgroup_stats.cpu_throttled_usec))
+      }),
+      unless_reset(delta.reset, || rate(cgroup_stats.io_read_bytes)),
+      unless_reset(delta.reset, || rate(cgroup_stats.io_write_bytes))
+    ];
 
-    let value = &humanize(proc.status.vm_swap);
-    mvaddnstr(line, COLUMNS[4].position, value, COLUMNS[4].width);
+    for (column, value) in CGROUP_COLUMNS.iter().zip(values.iter()) {
+      mvaddnstr(line, column.position, value, column.width);
+    }
+  }
 
-    let value = &humanize(proc.status.vm_rss + proc.status.vm_swap);
-    mvaddnstr(line, COLUMNS[5].position, value, COLUMNS[5].width);
+  /// Asks for a line of text in the last line of the screen, returns `None` if
+  /// cancelled with ESC
+  pub fn prompt(&mut self, message: &str) -> Option<String> {
+    let line = getmaxy(stdscr()) - 1;
+    let mut input = String::new();
 
-    let value = proc.io.bytes();
-    let value = if self.elapsed_time != 0f32 {
-      let rate = (value as f32 / self.elapsed_time) as u64;
-      humanize(rate) + "/s"
-    } else {
-      "0 B/s".to_string()
+    timeout(-1);
+    let result = loop {
+      mv(line, 0);
+      clrtoeol();
+      mvaddnstr(line, 0, &format!("{message}{input}"), 200);
+      ncurses::refresh();
+
+      match getch() {
+        27 => break None,
+        10 | 13 | ncurses::KEY_ENTER => break Some(input),
+        8 | 127 | ncurses::KEY_BACKSPACE => { input.pop(); },
+        key if (32..127).contains(&key) => input.push(key as u8 as char),
+        _ => {}
+      }
     };
-    mvaddnstr(line, COLUMNS[6].position, &value, COLUMNS[6].width);
+    timeout(self.delay_ms);
 
-    if let Some(color) = color {
-      attroff(COLOR_PAIR(color));
-    }
-    self.line += 1;
+    result
+  }
+
+  /// Asks a yes/no question, defaults to no
+  pub fn confirm(&mut self, message: &str) -> bool {
+    let answer = self.prompt(&format!("{message} [y/N] "));
+    matches!(answer.as_deref(), Some("y" | "Y" | "yes"))
+  }
+
+  pub fn set_status(&mut self, status: String) {
+    self.status = Some(status);
+  }
+
+  /// Lines left below the ones printed, the last one is kept for the status
+  pub fn lines_left(&self) -> usize {
+    (getmaxy(stdscr()) - 1 - self.line).max(0) as usize
+  }
+
+  /// Adds an error to the diagnostics panel, until the next refresh
+  pub fn report_error(&mut self, error: MonitorError) {
+    self.diagnostics.push(error.to_string());
   }
 
   pub fn clear(&mut self) {
     self.line = 0;
+    self.diagnostics.clear();
     ncurses::clear();
   }
 
+  /// Draws the diagnostics panel over the bottom lines, above the status line
+  fn print_diagnostics(&mut self) {
+    let mut lines: Vec<String> = self.diagnostics.iter()
+      .take(MAX_DIAGNOSTICS)
+      .cloned()
+      .collect();
+    if self.diagnostics.len() > MAX_DIAGNOSTICS {
+      let more = self.diagnostics.len() - MAX_DIAGNOSTICS + 1;
+      lines[MAX_DIAGNOSTICS - 1] = format!("... and {more} more errors");
+    }
+
+    let first_line = getmaxy(stdscr()) - 1 - lines.len() as i32;
+    attron(COLOR_PAIR(3));
+    for (i, diagnostic) in lines.iter().enumerate() {
+      let line = first_line + i as i32;
+      mv(line, 0);
+      clrtoeol();
+      mvaddnstr(line, 0, diagnostic, 200);
+    }
+    attroff(COLOR_PAIR(3));
+  }
+
   pub fn refresh(&mut self) {
+    if !self.diagnostics.is_empty() {
+      self.print_diagnostics();
+    }
+    if let Some(status) = &self.status {
+      let line = getmaxy(stdscr()) - 1;
+      mv(line, 0);
+      clrtoeol();
+      mvaddnstr(line, 0, status, 200);
+    }
     ncurses::refresh();
   }
 
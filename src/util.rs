@@ -35,6 +35,16 @@ pub fn humanize(mut value: u64) -> String {
   result
 }
 
+/// Size of a memory page, 4 KiB if unknown
+pub fn page_size() -> u64 {
+  let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+  if page_size > 0 {
+    page_size as u64
+  } else {
+    4096
+  }
+}
+
 #[test]
 fn humanize_returns_expected_values() {
   assert_eq!("0 B",      humanize(0));